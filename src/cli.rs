@@ -0,0 +1,916 @@
+use std::{collections::BTreeMap, fs, io::{self, BufRead, IsTerminal, Write}, panic, path::{Path, PathBuf}, process::ExitCode, thread, time::{Duration, Instant}};
+
+use clap::{Parser, Subcommand};
+use rayon::{prelude::*, ThreadPoolBuilder};
+use serde_json::json;
+
+use crate::{get_day, get_days, Day, day1::{self, Sonar}, error::AocError, solution::{Answer, Part}};
+use crate::report::{self, Format, Record};
+use crate::utils::{answers::{ExpectedAnswers, Manifest, Verdict, DEFAULT_PROFILE}, input::{self, InputProvider, InputSource}};
+use crate::utils::bench::{self, Baseline, Phase, Stats};
+use crate::utils::image::{self as image, NIGHT};
+use crate::utils::{fuzz, gen};
+
+#[derive(Debug, Parser)]
+#[command(name = "aoc-2021", about = "Solutions for Advent of Code 2021", args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    /// Days to run when no subcommand is given, e.g. `3-7,12` or `all`
+    #[arg(value_parser = parse_selection)]
+    days: Option<Selection>,
+
+    /// Read the puzzle input from this file, or `-` for stdin, only valid for a single day
+    #[arg(long, global = true, value_name = "PATH")]
+    input: Option<PathBuf>,
+
+    /// Use the inputs and answers of this profile, stored in <input-dir>/<profile>/
+    #[arg(long, global = true, value_parser = parse_profile)]
+    profile: Option<String>,
+
+    /// Directory holding the downloaded inputs
+    #[arg(long, global = true, value_name = "DIR", default_value = "input")]
+    input_dir: PathBuf,
+
+    /// Only use inputs that are already in the input directory, never touch the network
+    #[arg(long, global = true, visible_alias = "offline")]
+    no_download: bool,
+
+    /// Download inputs from this http:// stand-in for adventofcode.com, using $AOC_SESSION
+    #[arg(long, global = true, value_name = "URL")]
+    server: Option<String>,
+
+    #[arg(long, global = true, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run the selected days, e.g. `3-7,12` or `all`
+    Run {
+        #[arg(default_value = "all", value_parser = parse_selection)]
+        days: Selection,
+        /// Solve up to this many days at the same time
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        jobs: u32,
+        /// Read the input line by line for days that can solve it in one pass, in constant memory
+        #[arg(long)]
+        stream: bool,
+    },
+    /// Run a single part of one day
+    Part {
+        #[arg(value_parser = parse_day)]
+        day: u32,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+    },
+    /// List all available days
+    List,
+    /// Time loading the input, parsing and both parts of the selected days
+    Bench {
+        #[arg(default_value = "all", value_parser = parse_selection)]
+        days: Selection,
+        /// How often to run every day
+        #[arg(short = 'n', long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        iterations: u32,
+        /// Median timings of an earlier run to compare against
+        #[arg(long, value_name = "PATH", default_value = "bench_baseline.json")]
+        baseline: PathBuf,
+        /// Store the timings of this run in the baseline file
+        #[arg(long)]
+        save: bool,
+        /// Percentage by which a phase has to be slower than the baseline to count as a regression
+        #[arg(long, value_name = "PERCENT", default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Run the selected days and compare them against the expected answers
+    Check {
+        #[arg(default_value = "all", value_parser = parse_selection)]
+        days: Selection,
+        /// Answer manifest, keyed by profile, day and part
+        #[arg(long, value_name = "PATH", default_value = "answers.json")]
+        answers: PathBuf,
+        /// Also fail on parts without an expected answer
+        #[arg(long)]
+        strict: bool,
+    },
+    /// Play the states a simulation day passes through, one frame at a time
+    Visualize {
+        #[arg(value_parser = parse_day)]
+        day: u32,
+        /// Milliseconds to wait between frames
+        #[arg(long, value_name = "MS", default_value_t = 100)]
+        delay: u64,
+        /// Wait for Enter before every frame instead of the delay
+        #[arg(long)]
+        step: bool,
+        /// Stop after this many frames
+        #[arg(long, value_name = "N")]
+        max_frames: Option<usize>,
+    },
+    /// Write the states of a day as an animated GIF, or as numbered PNGs into a directory
+    Export {
+        #[arg(value_parser = parse_day)]
+        day: u32,
+        /// A .gif file, anything else is taken as the directory for the PNGs
+        path: PathBuf,
+        /// Pixels per cell along each side
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..))]
+        scale: u32,
+        /// Milliseconds every GIF frame is shown for
+        #[arg(long, value_name = "MS", default_value_t = 100)]
+        delay: u64,
+        /// Stop after this many frames
+        #[arg(long, value_name = "N")]
+        max_frames: Option<usize>,
+    },
+    /// Make up a random input for a day but 23 and 24, written into the profile with --profile or to stdout
+    Generate {
+        #[arg(value_parser = parse_generator)]
+        day: u32,
+        /// How large the input gets, what that means depends on the day. Defaults to about the size of a real input
+        #[arg(long, value_name = "N")]
+        size: Option<usize>,
+        /// The same seed always gives the same input
+        #[arg(long, default_value_t = 2021)]
+        seed: u64,
+        /// Write the input to this file instead
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+    /// Compare every window of day 1's soundings with the window before, reading the input line by line
+    Sonar {
+        /// How many soundings every window adds up
+        #[arg(short, long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+        window: u32,
+        /// Also list every window that got deeper, shallower or stayed the same
+        #[arg(long)]
+        changes: bool,
+    },
+    /// Solve mutated puzzle examples of a day with a hand-written parser (10, 16 or 18) until one panics
+    Fuzz {
+        #[arg(value_parser = parse_fuzz_target)]
+        day: u32,
+        /// How many inputs to try
+        #[arg(short = 'n', long, default_value_t = 100_000)]
+        runs: usize,
+        /// The same seed always tries the same inputs
+        #[arg(long, default_value_t = 2021)]
+        seed: u64,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Selection(Vec<u32>);
+
+pub fn parse_selection(input: &str) -> Result<Selection, String> {
+    if input == "all" {
+        return Ok(Selection(get_days().iter().map(|day| day.number).collect()));
+    }
+    let mut days = Vec::new();
+    for range in input.split(',').map(|range| range.trim()) {
+        match range.split_once('-') {
+            Some((start, end)) => {
+                let start = parse_day(start)?;
+                let end = parse_day(end)?;
+                if start > end {
+                    return Err(format!("Range {} runs backwards", range));
+                }
+                days.extend(start..=end);
+            },
+            None => days.push(parse_day(range)?),
+        }
+    }
+    days.sort_unstable();
+    days.dedup();
+    Ok(Selection(days))
+}
+
+fn parse_day(input: &str) -> Result<u32, String> {
+    let day = input.trim().parse::<u32>()
+        .map_err(|_| format!("{} is not a day", input))?;
+    match get_day(day) {
+        Some(_) => Ok(day),
+        None => Err(format!("There is no solution for day {}", day)),
+    }
+}
+
+fn parse_fuzz_target(input: &str) -> Result<u32, String> {
+    let day = parse_day(input)?;
+    match fuzz::target(day) {
+        Some(_) => Ok(day),
+        None => Err(format!("Day {} has no fuzz target, try {}", day, fuzz::targets().iter().map(|target| target.day.to_string()).collect::<Vec<_>>().join(", "))),
+    }
+}
+
+fn parse_generator(input: &str) -> Result<u32, String> {
+    let day = parse_day(input)?;
+    match gen::generator(day) {
+        Some(_) => Ok(day),
+        None => Err(format!("Day {} only solves one particular input, so it has no generator", day)),
+    }
+}
+
+fn parse_profile(input: &str) -> Result<String, String> {
+    if input::is_valid_profile(input) {
+        Ok(input.to_string())
+    } else {
+        Err("Profile names may only contain letters, digits, - and _".to_string())
+    }
+}
+
+struct DayResult {
+    day: &'static Day,
+    parts: [(Result<Answer, String>, Duration); 2],
+    elapsed: Duration,
+}
+
+impl DayResult {
+    // Both parts come out of the same pass over the input, the first one is charged for all of it
+    fn streamed(day: &'static Day, outcome: Result<(Answer, Answer), String>, elapsed: Duration) -> Self {
+        let parts = match outcome {
+            Ok((part1, part2)) => [(Ok(part1), elapsed), (Ok(part2), Duration::ZERO)],
+            Err(error) => [(Err(error.clone()), elapsed), (Err(error), Duration::ZERO)],
+        };
+        DayResult { day, parts, elapsed }
+    }
+
+    fn is_ok(&self) -> bool {
+        self.parts.iter().all(|(outcome, _)| outcome.is_ok())
+    }
+
+    fn records(&self) -> impl Iterator<Item = Record> + '_ {
+        [Part::One, Part::Two].into_iter()
+            .zip(&self.parts)
+            .map(|(part, (outcome, duration))| Record::new(self.day.number, self.day.name, part, outcome, *duration))
+    }
+}
+
+struct BenchResult {
+    day: &'static Day,
+    outcome: Result<BTreeMap<Phase, Stats>, String>,
+}
+
+pub fn run() -> ExitCode {
+    let cli = Cli::parse();
+    match &cli.command {
+        None => {
+            let days = cli.days.clone().unwrap_or_else(|| parse_selection("all").unwrap());
+            cli.run_days(&days, 1, false)
+        },
+        Some(Command::Run { days, jobs, stream }) => cli.run_days(days, *jobs, *stream),
+        Some(Command::Part { day, part }) => cli.run_part(*day, Part::try_from(*part).unwrap()),
+        Some(Command::List) => cli.list(),
+        Some(Command::Bench { days, iterations, baseline, save, threshold }) => cli.bench(days, *iterations, baseline, *save, *threshold),
+        Some(Command::Check { days, answers, strict }) => cli.check(days, answers, *strict),
+        Some(Command::Visualize { day, delay, step, max_frames }) => cli.visualize(*day, Duration::from_millis(*delay), *step, *max_frames),
+        Some(Command::Export { day, path, scale, delay, max_frames }) => cli.export(*day, path, *scale as usize, Duration::from_millis(*delay), *max_frames),
+        Some(Command::Generate { day, size, seed, output }) => cli.generate(*day, *size, *seed, output.as_deref()),
+        Some(Command::Sonar { window, changes }) => cli.sonar(*window as usize, *changes),
+        Some(Command::Fuzz { day, runs, seed }) => fuzz(*day, *runs, *seed),
+    }
+}
+
+impl Cli {
+    fn input_provider(&self) -> InputProvider {
+        let mut provider = InputProvider::new(&self.input_dir);
+        if self.no_download {
+            provider = provider.offline(true);
+        }
+        if let Some(profile) = &self.profile {
+            provider = provider.with_profile(profile).unwrap();
+        }
+        match (&self.input, &self.server) {
+            (Some(path), _) if path == Path::new("-") => provider.with_sources(vec![InputSource::Stdin]),
+            (Some(path), _) => provider.with_sources(vec![InputSource::File(path.clone())]),
+            (None, Some(url)) => provider.with_sources(vec![
+                InputSource::Cache,
+                InputSource::Server { url: url.clone(), session: std::env::var("AOC_SESSION").ok() },
+            ]),
+            (None, None) => provider,
+        }
+    }
+
+    fn load_input(&self, day: &Day) -> Result<String, String> {
+        day.load_input(&self.input_provider()).map_err(|error| error.to_string())
+    }
+
+    fn check_input_flag(&self, days: &Selection) -> Result<(), ExitCode> {
+        if self.input.is_some() && days.0.len() != 1 {
+            eprintln!("error: --input can only be used with a single day");
+            return Err(ExitCode::from(2));
+        }
+        Ok(())
+    }
+
+    // Results come back in the order of days, however many of them are solved at once
+    fn solve_days(&self, days: &Selection, jobs: u32, stream: bool) -> Result<Vec<DayResult>, ExitCode> {
+        self.check_input_flag(days)?;
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(jobs as usize)
+            .build()
+            .unwrap();
+        Ok(pool.install(|| days.0.par_iter()
+            .map(|&number| {
+                let day = get_day(number).unwrap();
+                let start = Instant::now();
+                if stream {
+                    if let Some(outcome) = catch(|| day.solve_stream(&self.input_provider())).transpose() {
+                        return DayResult::streamed(day, outcome, start.elapsed());
+                    }
+                }
+                let parts = match self.load_input(day).and_then(|input| catch(|| day.solve_timed(&input))) {
+                    Ok(timed) => timed.parts.map(|(outcome, duration)| (outcome.map_err(|error| error.to_string()), duration)),
+                    Err(error) => [(Err(error.clone()), Duration::ZERO), (Err(error), Duration::ZERO)],
+                };
+                DayResult { day, parts, elapsed: start.elapsed() }
+            })
+            .collect()))
+    }
+
+    fn run_days(&self, days: &Selection, jobs: u32, stream: bool) -> ExitCode {
+        let start = Instant::now();
+        let results = match self.solve_days(days, jobs, stream) {
+            Ok(results) => results,
+            Err(code) => return code,
+        };
+        let wall = start.elapsed();
+        let records: Vec<_> = results.iter().flat_map(DayResult::records).collect();
+        report::write(self.format, &records, &mut io::stdout().lock()).unwrap();
+        if self.format == Format::Text {
+            print_summary(&results, wall, jobs);
+        }
+        exit_code(&results)
+    }
+
+    fn run_part(&self, number: u32, part: Part) -> ExitCode {
+        let day = get_day(number).unwrap();
+        let start = Instant::now();
+        let outcome = self.load_input(day)
+            .and_then(|input| catch(|| day.solve_part(&input, part)));
+        match (self.format, &outcome) {
+            // Nothing but the answer, so it can be piped into other tools
+            (Format::Text, Ok(answer)) => println!("{}", answer),
+            (Format::Text, Err(error)) => eprintln!("Day {} part {} failed: {}", number, part, error),
+            (format, _) => {
+                let record = Record::new(number, day.name, part, &outcome, start.elapsed());
+                report::write(format, &[record], &mut io::stdout().lock()).unwrap();
+            },
+        }
+        if outcome.is_ok() { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+    }
+
+    fn list(&self) -> ExitCode {
+        match self.format {
+            Format::Text => for day in get_days() {
+                println!("{:>2}  {}", day.number, day.name);
+            },
+            Format::Json => {
+                let days: Vec<_> = get_days().iter()
+                    .map(|day| json!({ "day": day.number, "name": day.name }))
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&days).unwrap());
+            },
+            Format::Csv => {
+                let mut out = io::stdout().lock();
+                report::write_csv_row(&mut out, &["day", "name"]).unwrap();
+                for day in get_days() {
+                    report::write_csv_row(&mut out, &[&day.number.to_string(), day.name]).unwrap();
+                }
+            },
+        }
+        ExitCode::SUCCESS
+    }
+
+    fn bench(&self, days: &Selection, iterations: u32, baseline_path: &Path, save: bool, threshold: f64) -> ExitCode {
+        if let Err(code) = self.check_input_flag(days) {
+            return code;
+        }
+        let mut baseline = match Baseline::load(baseline_path) {
+            Ok(baseline) => baseline,
+            Err(error) => {
+                eprintln!("error: {}", error);
+                return ExitCode::FAILURE;
+            },
+        };
+        let provider = self.input_provider();
+        let results: Vec<_> = days.0.iter()
+            .map(|&number| {
+                let day = get_day(number).unwrap();
+                let outcome = (0..iterations)
+                    .map(|_| catch(|| day.time(&provider)))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(|timings| bench::summarize(&timings).unwrap());
+                BenchResult { day, outcome }
+            })
+            .collect();
+
+        let regressed = |day: u32, phase: Phase, stats: &Stats| baseline.get(day, phase)
+            .filter(|&before| bench::is_regression(before, stats.median, threshold));
+        let mut regressions = 0;
+        let mut total = Duration::ZERO;
+        let mut days_json = Vec::new();
+        let mut out = io::stdout().lock();
+        if self.format == Format::Csv {
+            report::write_csv_row(&mut out, &["day", "phase", "min", "median", "max", "baseline", "regressed", "error"]).unwrap();
+        }
+        for result in &results {
+            let stats = match &result.outcome {
+                Ok(stats) => stats,
+                Err(error) => {
+                    match self.format {
+                        Format::Text => println!("Day {:>2}  {:<24} failed: {}", result.day.number, result.day.name, error),
+                        Format::Json => days_json.push(json!({
+                            "day": result.day.number,
+                            "name": result.day.name,
+                            "error": error,
+                        })),
+                        Format::Csv => {
+                            let day = result.day.number.to_string();
+                            report::write_csv_row(&mut out, &[&day, "", "", "", "", "", "", error]).unwrap();
+                        },
+                    }
+                    continue;
+                },
+            };
+            if self.format == Format::Text {
+                println!("Day {:>2}  {:<24} {:>12} {:>12} {:>12}", result.day.number, result.day.name, "min", "median", "max");
+            }
+            let mut phases = serde_json::Map::new();
+            for (&phase, stats) in stats {
+                total += stats.median;
+                let regression = regressed(result.day.number, phase, stats);
+                regressions += regression.is_some() as usize;
+                match self.format {
+                    Format::Text => {
+                        let flag = match regression {
+                            Some(before) => format!("  REGRESSED from {:.3?}", before),
+                            None => String::new(),
+                        };
+                        println!("    {:<28} {:>12.3?} {:>12.3?} {:>12.3?}{}", phase.name(), stats.min, stats.median, stats.max, flag);
+                    },
+                    Format::Json => {
+                        phases.insert(phase.name().to_string(), json!({
+                            "min": stats.min.as_secs_f64(),
+                            "median": stats.median.as_secs_f64(),
+                            "max": stats.max.as_secs_f64(),
+                            "baseline": baseline.get(result.day.number, phase).map(|before| before.as_secs_f64()),
+                            "regressed": regression.is_some(),
+                        }));
+                    },
+                    Format::Csv => {
+                        let before = baseline.get(result.day.number, phase)
+                            .map(|before| before.as_secs_f64().to_string())
+                            .unwrap_or_default();
+                        report::write_csv_row(&mut out, &[
+                            &result.day.number.to_string(),
+                            phase.name(),
+                            &stats.min.as_secs_f64().to_string(),
+                            &stats.median.as_secs_f64().to_string(),
+                            &stats.max.as_secs_f64().to_string(),
+                            &before,
+                            &regression.is_some().to_string(),
+                            "",
+                        ]).unwrap();
+                    },
+                }
+            }
+            if self.format == Format::Json {
+                days_json.push(json!({
+                    "day": result.day.number,
+                    "name": result.day.name,
+                    "phases": phases,
+                }));
+            }
+        }
+        match self.format {
+            Format::Text => {
+                println!("Total of medians over {} iterations {:>21.3?}", iterations, total);
+                if regressions > 0 {
+                    println!("{} phases are more than {}% slower than {}", regressions, threshold, baseline_path.display());
+                }
+            },
+            Format::Json => println!("{}", serde_json::to_string_pretty(&json!({
+                "iterations": iterations,
+                "days": days_json,
+                "total_seconds": total.as_secs_f64(),
+                "regressions": regressions,
+            })).unwrap()),
+            Format::Csv => (),
+        }
+
+        if save {
+            for result in &results {
+                if let Ok(stats) = &result.outcome {
+                    baseline.insert(result.day.number, stats);
+                }
+            }
+            if let Err(error) = baseline.save(baseline_path) {
+                eprintln!("error: {}", error);
+                return ExitCode::FAILURE;
+            }
+            eprintln!("Saved baseline to {}", baseline_path.display());
+        }
+        if regressions == 0 && results.iter().all(|result| result.outcome.is_ok()) {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        }
+    }
+
+    fn expected_answers(&self, manifest: &Path) -> Result<ExpectedAnswers, String> {
+        let provider = self.input_provider();
+        let mut expected = Manifest::load(manifest)
+            .map_err(|error| error.to_string())?
+            .profile(provider.profile().unwrap_or(DEFAULT_PROFILE));
+        let local = ExpectedAnswers::load(&provider.answers_path())
+            .map_err(|error| error.to_string())?;
+        expected.merge(local);
+        Ok(expected)
+    }
+
+    fn check(&self, days: &Selection, manifest: &Path, strict: bool) -> ExitCode {
+        let known = match self.expected_answers(manifest) {
+            Ok(known) => known,
+            Err(error) => {
+                eprintln!("error: {}", error);
+                return ExitCode::FAILURE;
+            },
+        };
+        let results = match self.solve_days(days, 1, false) {
+            Ok(results) => results,
+            Err(code) => return code,
+        };
+        let verdicts: Vec<_> = results.iter()
+            .flat_map(|result| [Part::One, Part::Two].into_iter()
+                .zip(&result.parts)
+                .map(|(part, (actual, _))| (result.day.number, part, Verdict::judge(known.get(result.day.number, part), actual))))
+            .collect();
+
+        match self.format {
+            Format::Text => {
+                for (day, part, verdict) in &verdicts {
+                    println!("Day {:>2} part {}: {}", day, part, verdict);
+                }
+                let count = |status| verdicts.iter().filter(|(_, _, verdict)| verdict.status() == status).count();
+                println!("{} passed, {} failed, {} errors, {} missing", count("pass"), count("fail"), count("error"), count("missing"));
+            },
+            Format::Json => {
+                let parts: Vec<_> = verdicts.iter()
+                    .map(|(day, part, verdict)| {
+                        let (expected, actual, error) = verdict_fields(&known, *day, *part, verdict);
+                        json!({
+                            "day": day,
+                            "part": part.to_string(),
+                            "status": verdict.status(),
+                            "expected": expected,
+                            "actual": actual,
+                            "error": error,
+                        })
+                    })
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&parts).unwrap());
+            },
+            Format::Csv => {
+                let mut out = io::stdout().lock();
+                report::write_csv_row(&mut out, &["day", "part", "status", "expected", "actual", "error"]).unwrap();
+                for (day, part, verdict) in &verdicts {
+                    let (expected, actual, error) = verdict_fields(&known, *day, *part, verdict);
+                    report::write_csv_row(&mut out, &[
+                        &day.to_string(),
+                        &part.to_string(),
+                        verdict.status(),
+                        expected.unwrap_or_default(),
+                        actual.unwrap_or_default(),
+                        error.unwrap_or_default(),
+                    ]).unwrap();
+                }
+            },
+        }
+
+        let failed = verdicts.iter()
+            .any(|(_, _, verdict)| verdict.is_failure() || (strict && verdict.status() == "missing"));
+        if failed { ExitCode::FAILURE } else { ExitCode::SUCCESS }
+    }
+
+    fn visualize(&self, number: u32, delay: Duration, step: bool, max_frames: Option<usize>) -> ExitCode {
+        let day = get_day(number).unwrap();
+        let frames = match self.load_input(day).and_then(|input| catch(|| day.frames(&input))) {
+            Ok(Some(frames)) => frames,
+            Ok(None) => {
+                eprintln!("error: day {} is not a simulation, there is nothing to show", number);
+                return ExitCode::FAILURE;
+            },
+            Err(error) => {
+                eprintln!("Day {} failed: {}", number, error);
+                return ExitCode::FAILURE;
+            },
+        };
+
+        // Redraw in place on a terminal, anywhere else the frames simply follow each other
+        let terminal = io::stdout().is_terminal();
+        let mut stdin = io::stdin().lock();
+        let mut out = io::stdout().lock();
+        for (idx, frame) in frames.take(max_frames.unwrap_or(usize::MAX)).enumerate() {
+            if idx > 0 {
+                if step {
+                    if stdin.read_line(&mut String::new()).unwrap_or(0) == 0 {
+                        break;
+                    }
+                } else {
+                    thread::sleep(delay);
+                }
+            }
+            if terminal {
+                write!(out, "\x1b[2J\x1b[H").unwrap();
+            } else if idx > 0 {
+                writeln!(out).unwrap();
+            }
+            write!(out, "{}", frame).unwrap();
+            out.flush().unwrap();
+        }
+        ExitCode::SUCCESS
+    }
+
+    fn export(&self, number: u32, path: &Path, scale: usize, delay: Duration, max_frames: Option<usize>) -> ExitCode {
+        let day = get_day(number).unwrap();
+        let images = match self.load_input(day).and_then(|input| catch(|| day.images(&input))) {
+            Ok(Some(images)) => images.take(max_frames.unwrap_or(usize::MAX)),
+            Ok(None) => {
+                eprintln!("error: day {} has no pictures to export", number);
+                return ExitCode::FAILURE;
+            },
+            Err(error) => {
+                eprintln!("Day {} failed: {}", number, error);
+                return ExitCode::FAILURE;
+            },
+        };
+
+        let written = if path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("gif")) {
+            let frames: Vec<_> = images.collect();
+            image::save_gif(path, &frames, scale, delay, NIGHT).map(|_| frames.len())
+        } else {
+            image::save_pngs(path, images, scale)
+        };
+        match written {
+            Ok(count) => {
+                println!("Wrote {} frame{} to {}", count, if count == 1 { "" } else { "s" }, path.display());
+                ExitCode::SUCCESS
+            },
+            Err(error) => {
+                eprintln!("error: {}", error);
+                ExitCode::FAILURE
+            },
+        }
+    }
+
+    fn generate(&self, number: u32, size: Option<usize>, seed: u64, output: Option<&Path>) -> ExitCode {
+        let generator = gen::generator(number).unwrap();
+        let input = generator.generate(seed, size.unwrap_or(generator.default_size));
+        // A generated profile can be run, checked and benchmarked like any other one
+        let path = match (output, &self.profile) {
+            (Some(path), _) => path.to_path_buf(),
+            (None, Some(_)) => self.input_provider().cache_path(number),
+            (None, None) => {
+                print!("{}", input);
+                return ExitCode::SUCCESS;
+            },
+        };
+        let written = path.parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, &input));
+        match written {
+            Ok(()) => {
+                println!("Wrote the input of day {} to {}", number, path.display());
+                ExitCode::SUCCESS
+            },
+            Err(error) => {
+                eprintln!("error: Could not write {}: {}", path.display(), error);
+                ExitCode::FAILURE
+            },
+        }
+    }
+
+    fn sonar(&self, window: usize, list_changes: bool) -> ExitCode {
+        match self.write_sweep(&mut io::stdout().lock(), window, list_changes) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("Day 1 failed: {}", error);
+                ExitCode::FAILURE
+            },
+        }
+    }
+
+    // Only the current window is kept, so sweeps of any length fit into memory
+    fn write_sweep(&self, out: &mut impl Write, window: usize, list_changes: bool) -> crate::error::Result<()> {
+        let stdout = |error: io::Error| AocError::Write(PathBuf::from("<stdout>"), error);
+        let mut sonar = Sonar::new(window);
+        let mut listed = 0;
+        match self.format {
+            Format::Csv if list_changes => report::write_csv_row(out, &["line", "change"]).map_err(stdout)?,
+            // The changes are written as they come, the summary only follows once the sweep is done
+            Format::Json if list_changes => write!(out, "{{\n  \"changes\": [").map_err(stdout)?,
+            _ => (),
+        }
+        let outcome = self.input_provider().lines(day1::DAY).and_then(|lines| {
+            for depth in day1::soundings(lines) {
+                match (sonar.push(depth?).filter(|_| list_changes), self.format) {
+                    (Some(change), Format::Text) => writeln!(out, "{:>8}  {}", change.line, change.describe()).map_err(stdout)?,
+                    (Some(change), Format::Csv) => report::write_csv_row(out, &[&change.line.to_string(), change.describe()]).map_err(stdout)?,
+                    (Some(change), Format::Json) => {
+                        let separator = if listed == 0 { "" } else { "," };
+                        write!(out, "{}\n    {}", separator, json!({ "line": change.line, "change": change.describe() })).map_err(stdout)?;
+                        listed += 1;
+                    },
+                    (None, _) => (),
+                }
+            }
+            Ok(())
+        });
+        if let Err(error) = outcome {
+            // The changes listed so far still make a whole document, a CSV table simply ends early
+            if self.format == Format::Json && list_changes {
+                write!(out, "\n  ],\n  \"error\": {}\n}}\n", json!(error.to_string())).map_err(stdout)?;
+            }
+            return Err(error);
+        }
+
+        let sweep = sonar.sweep();
+        match self.format {
+            Format::Text => writeln!(out, "Windows of {} soundings: {} deeper, {} shallower, {} unchanged, at most {} deeper in a row",
+                sweep.window, sweep.increases, sweep.decreases, sweep.unchanged, sweep.longest_rise).map_err(stdout),
+            Format::Json if list_changes => {
+                write!(out, "\n  ]").map_err(stdout)?;
+                for (key, value) in serde_json::to_value(&sweep).unwrap().as_object().unwrap() {
+                    write!(out, ",\n  {}: {}", json!(key), value).map_err(stdout)?;
+                }
+                writeln!(out, "\n}}").map_err(stdout)
+            },
+            Format::Json => writeln!(out, "{}", serde_json::to_string_pretty(&sweep).unwrap()).map_err(stdout),
+            Format::Csv if list_changes => Ok(()),
+            Format::Csv => {
+                report::write_csv_row(out, &["window", "increases", "decreases", "unchanged", "longest_rise"]).map_err(stdout)?;
+                let counts: Vec<String> = [sweep.window, sweep.increases, sweep.decreases, sweep.unchanged, sweep.longest_rise].iter()
+                    .map(|count| count.to_string())
+                    .collect();
+                report::write_csv_row(out, &counts.iter().map(String::as_str).collect::<Vec<_>>()).map_err(stdout)
+            },
+        }
+    }
+}
+
+fn fuzz(day: u32, runs: usize, seed: u64) -> ExitCode {
+    match fuzz::target(day).unwrap().run(seed, runs) {
+        None => {
+            println!("Day {} solved {} mutated inputs without panicking", day, runs);
+            ExitCode::SUCCESS
+        },
+        Some(crash) => {
+            eprintln!("error: Day {} panicked with '{}' on {:?}", day, crash.message, crash.input);
+            ExitCode::FAILURE
+        },
+    }
+}
+
+fn verdict_fields<'a>(known: &'a ExpectedAnswers, day: u32, part: Part, verdict: &'a Verdict) -> (Option<&'a str>, Option<&'a str>, Option<&'a str>) {
+    let expected = known.get(day, part);
+    match verdict {
+        Verdict::Pass => (expected, expected, None),
+        Verdict::Fail { actual, .. } | Verdict::Missing { actual } => (expected, Some(actual), None),
+        Verdict::Error(error) => (expected, None, Some(error)),
+    }
+}
+
+fn print_summary(results: &[DayResult], wall: Duration, jobs: u32) {
+    println!();
+    println!("Day  {:<24} {:>12}", "Name", "Time");
+    for result in results {
+        let status = if result.is_ok() { "" } else { "  (failed)" };
+        println!("{:>3}  {:<24} {:>12.3?}{}", result.day.number, result.day.name, result.elapsed, status);
+    }
+    let sum: Duration = results.iter().map(|result| result.elapsed).sum();
+    println!("     {:<24} {:>12.3?}", "Sum of all days", sum);
+    println!("     {:<24} {:>12.3?}", format!("Total on {} thread{}", jobs, if jobs == 1 { "" } else { "s" }), wall);
+}
+
+fn exit_code(results: &[DayResult]) -> ExitCode {
+    if results.iter().all(DayResult::is_ok) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+// Bad input is reported as an error, but a bug in one day must not take the others down with it
+fn catch<T>(solve: impl FnOnce() -> crate::error::Result<T>) -> Result<T, String> {
+    panic::catch_unwind(panic::AssertUnwindSafe(solve))
+        .map_err(|payload| {
+            if let Some(message) = payload.downcast_ref::<&str>() {
+                message.to_string()
+            } else if let Some(message) = payload.downcast_ref::<String>() {
+                message.clone()
+            } else {
+                "Solution panicked".to_string()
+            }
+        })
+        .and_then(|outcome| outcome.map_err(|error| error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_accepts_days_and_ranges() {
+        assert_eq!(Selection(vec![3, 4, 5, 6, 7, 12]), parse_selection("3-7,12").unwrap());
+        assert_eq!(Selection(vec![1, 2, 5]), parse_selection("5, 2,1,2").unwrap());
+        assert_eq!(25, parse_selection("all").unwrap().0.len());
+    }
+
+    #[test]
+    fn selection_rejects_unknown_days() {
+        assert!(parse_selection("0").is_err());
+        assert!(parse_selection("24-26").is_err());
+        assert!(parse_selection("7-3").is_err());
+        assert!(parse_selection("three").is_err());
+    }
+
+    #[test]
+    fn run_takes_days_jobs_and_stream() {
+        let cli = Cli::try_parse_from(["aoc-2021", "run", "3-7,12", "--format", "json", "--no-download"]).unwrap();
+        assert_eq!(Format::Json, cli.format);
+        assert!(cli.no_download);
+        assert!(matches!(cli.command, Some(Command::Run { days: Selection(ref days), jobs: 1, stream: false }) if days.len() == 6));
+        let cli = Cli::try_parse_from(["aoc-2021", "run", "all", "--jobs", "8", "--stream"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Run { jobs: 8, stream: true, .. })));
+        assert!(Cli::try_parse_from(["aoc-2021", "run", "all", "--jobs", "0"]).is_err());
+        assert!(Cli::try_parse_from(["aoc-2021", "12"]).unwrap().days.is_some());
+    }
+
+    #[test]
+    fn part_takes_a_day_and_a_part() {
+        let cli = Cli::try_parse_from(["aoc-2021", "part", "5", "2"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Part { day: 5, part: 2 })));
+        assert!(Cli::try_parse_from(["aoc-2021", "part", "5", "3"]).is_err());
+    }
+
+    #[test]
+    fn bench_takes_iterations_and_save() {
+        let cli = Cli::try_parse_from(["aoc-2021", "bench", "19,22", "-n", "3", "--save"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Bench { iterations: 3, save: true, .. })));
+        assert!(Cli::try_parse_from(["aoc-2021", "bench", "-n", "0"]).is_err());
+    }
+
+    #[test]
+    fn check_reads_answers_json_by_default() {
+        let cli = Cli::try_parse_from(["aoc-2021", "check", "--strict"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Check { ref answers, strict: true, .. }) if answers == Path::new("answers.json")));
+    }
+
+    #[test]
+    fn visualize_takes_delay_and_frame_limit() {
+        let cli = Cli::try_parse_from(["aoc-2021", "visualize", "11", "--delay", "0", "--max-frames", "5"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Visualize { day: 11, delay: 0, step: false, max_frames: Some(5) })));
+    }
+
+    #[test]
+    fn export_takes_a_path_and_scale() {
+        let cli = Cli::try_parse_from(["aoc-2021", "export", "25", "herds.gif", "--scale", "2"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Export { day: 25, scale: 2, delay: 100, max_frames: None, .. })));
+        assert!(Cli::try_parse_from(["aoc-2021", "export", "25", "herds.gif", "--scale", "0"]).is_err());
+    }
+
+    #[test]
+    fn generate_takes_size_and_seed() {
+        let cli = Cli::try_parse_from(["aoc-2021", "generate", "12", "--size", "8", "--profile", "synthetic"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Generate { day: 12, size: Some(8), seed: 2021, output: None })));
+        assert!(Cli::try_parse_from(["aoc-2021", "generate", "26"]).is_err());
+        assert!(Cli::try_parse_from(["aoc-2021", "generate", "23"]).is_err());
+    }
+
+    #[test]
+    fn fuzz_only_takes_days_with_a_target() {
+        let cli = Cli::try_parse_from(["aoc-2021", "fuzz", "16", "-n", "50"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Fuzz { day: 16, runs: 50, seed: 2021 })));
+        assert!(Cli::try_parse_from(["aoc-2021", "fuzz", "3"]).is_err());
+    }
+
+    #[test]
+    fn sonar_takes_a_window() {
+        let cli = Cli::try_parse_from(["aoc-2021", "sonar", "--window", "5", "--changes"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Sonar { window: 5, changes: true })));
+        assert!(Cli::try_parse_from(["aoc-2021", "sonar", "--window", "0"]).is_err());
+    }
+
+    #[test]
+    fn input_flags_select_the_sources() {
+        let cli = Cli::try_parse_from(["aoc-2021", "run", "1", "--input", "-"]).unwrap();
+        assert_eq!(&[InputSource::Stdin], cli.input_provider().sources());
+
+        let cli = Cli::try_parse_from(["aoc-2021", "run", "1", "--offline", "--input-dir", "inputs"]).unwrap();
+        assert!(cli.input_provider().is_offline());
+        assert_eq!(Path::new("inputs/input1.txt"), cli.input_provider().cache_path(1));
+    }
+}
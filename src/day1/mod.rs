@@ -1,51 +1,153 @@
-use aoc_downloader::download_day;
+use std::{cmp::Ordering, collections::VecDeque};
 
-const DAY: u32 = 1;
+use serde::Serialize;
 
-fn get_input() -> Vec<String> {
-    use std::io::BufRead;
-    download_day((DAY) as u32, "input").unwrap();
+use crate::error::Result;
+use crate::utils::{input::InputReader, parse};
+use crate::solution::{Answer, Solution};
 
-    let file = std::fs::File::open(format!("input/input{}.txt", DAY)).unwrap();
-    let reader = std::io::BufReader::new(&file);
-    reader.lines().collect::<Result<_, _>>().unwrap()
+pub(crate) const DAY: u32 = 1;
+
+fn parse_input(input: &str) -> Result<Vec<u32>> {
+    parse::lines(input, |line| parse::number(line, 1))
 }
 
-fn parse_input(input: Vec<String>) -> Vec<u32> {
-    input
-        .iter()
-        .map(|v| v.parse::<u32>().unwrap())
-        .collect::<Vec<_>>()
+pub(crate) fn soundings(lines: impl Iterator<Item = Result<String>>) -> impl Iterator<Item = Result<u32>> {
+    parse::stream(lines, |line| parse::number(line, 1))
 }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(input);
-    println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
+pub(crate) struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<u32>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
+
+    fn stream(&self, input: InputReader) -> Option<Result<(Answer, Answer)>> {
+        Some(count_increases(soundings(input.lines())).map(|(part1, part2)| (part1.into(), part2.into())))
+    }
 }
 
 fn part1(input: &Vec<u32>) -> usize {
-    input.windows(2)
-        .filter(|w| w[1] > w[0])
-        .count()
+    increases(input, 1)
 }
 
 fn part2(input: &Vec<u32>) -> usize {
-    part1(&input.windows(3)
-        .map(|w| w.iter().sum())
-        .collect::<Vec<_>>())
+    increases(input, 3)
+}
+
+fn increases(input: &[u32], window: usize) -> usize {
+    let mut sonar = Sonar::new(window);
+    for &depth in input {
+        sonar.push(depth);
+    }
+    sonar.sweep().increases
+}
+
+// Both parts at once, in one pass over the soundings
+fn count_increases(soundings: impl Iterator<Item = Result<u32>>) -> Result<(usize, usize)> {
+    let (mut single, mut triple) = (Sonar::new(1), Sonar::new(3));
+    for depth in soundings {
+        let depth = depth?;
+        single.push(depth);
+        triple.push(depth);
+    }
+    Ok((single.sweep().increases, triple.sweep().increases))
+}
+
+// How the sums of every window of soundings compare with the window just before
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub(crate) struct Sweep {
+    pub window: usize,
+    pub increases: usize,
+    pub decreases: usize,
+    pub unchanged: usize,
+    // The most increases in a row
+    pub longest_rise: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Change {
+    // The line of the sounding that completes the later window
+    pub line: usize,
+    pub direction: Ordering,
+}
+
+impl Change {
+    pub(crate) fn describe(&self) -> &'static str {
+        match self.direction {
+            Ordering::Greater => "deeper",
+            Ordering::Less => "shallower",
+            Ordering::Equal => "unchanged",
+        }
+    }
+}
+
+// Takes one sounding at a time and only keeps the current window
+pub(crate) struct Sonar {
+    window: VecDeque<u32>,
+    soundings: usize,
+    rise: usize,
+    sweep: Sweep,
+}
+
+impl Sonar {
+    pub(crate) fn new(window: usize) -> Self {
+        assert!(window > 0, "a window needs at least one sounding");
+        Sonar {
+            window: VecDeque::with_capacity(window + 1),
+            soundings: 0,
+            rise: 0,
+            sweep: Sweep { window, ..Sweep::default() },
+        }
+    }
+
+    // None until there are two windows to compare
+    pub(crate) fn push(&mut self, depth: u32) -> Option<Change> {
+        self.soundings += 1;
+        self.window.push_back(depth);
+        if self.window.len() <= self.sweep.window {
+            return None;
+        }
+        // Neighbouring windows share all soundings but the first and the last, so only those decide
+        let direction = depth.cmp(&self.window.pop_front().unwrap());
+        match direction {
+            Ordering::Greater => {
+                self.sweep.increases += 1;
+                self.rise += 1;
+                self.sweep.longest_rise = self.sweep.longest_rise.max(self.rise);
+            },
+            Ordering::Less => {
+                self.sweep.decreases += 1;
+                self.rise = 0;
+            },
+            Ordering::Equal => {
+                self.sweep.unchanged += 1;
+                self.rise = 0;
+            },
+        }
+        Some(Change { line: self.soundings, direction })
+    }
+
+    pub(crate) fn sweep(&self) -> Sweep {
+        self.sweep.clone()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn day1_part1_output() {
-        let input = parse_input(get_input());
-        assert_eq!(1215, part1(&input));
-    }
-
     #[test]
     fn day1_part1_testcase1() {
         let input = vec![199, 200, 208, 210, 200, 207, 240, 269, 260,263];
@@ -53,8 +155,31 @@ mod tests {
     }
 
     #[test]
-    fn day1_part2_output() {
-        let input = parse_input(get_input());
-        assert_eq!(1150, part2(&input));
+    fn day1_streams_both_parts() {
+        let input = vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        assert_eq!(5, part2(&input));
+        assert_eq!((7, 5), count_increases(input.into_iter().map(Ok)).unwrap());
+
+        let reader = InputReader::from_string("199\n200\nx\n".to_string());
+        assert!(Day1.stream(reader).unwrap().is_err());
+    }
+
+    #[test]
+    fn day1_sweeps_any_window() {
+        let input = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        let sweep = |window| {
+            let mut sonar = Sonar::new(window);
+            let changes: Vec<_> = input.into_iter().filter_map(|depth| sonar.push(depth)).collect();
+            (sonar.sweep(), changes)
+        };
+        assert_eq!(Sweep { window: 1, increases: 7, decreases: 2, unchanged: 0, longest_rise: 3 }, sweep(1).0);
+
+        let (triple, changes) = sweep(3);
+        assert_eq!(Sweep { window: 3, increases: 5, decreases: 1, unchanged: 1, longest_rise: 4 }, triple);
+        assert_eq!(Change { line: 4, direction: Ordering::Greater }, changes[0]);
+        assert_eq!(Change { line: 5, direction: Ordering::Equal }, changes[1]);
+
+        // A window as long as the sweep has nothing to compare with
+        assert_eq!((Sweep { window: 10, ..Sweep::default() }, vec![]), sweep(10));
     }
 }
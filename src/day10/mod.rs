@@ -1,23 +1,33 @@
-use aoc_downloader::download_day;
+use crate::error::{AocError, Result};
+use crate::utils::parse;
+use crate::solution::{Answer, Solution};
 
-const DAY: u32 = 10;
+pub(crate) const DAY: u32 = 10;
 type InputType = String;
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
+fn parse_input(input: &str) -> Result<Vec<InputType>> {
+    parse::lines(input, |line| match line.char_indices().find(|(_, c)| !"()[]{}<>".contains(*c)) {
+        Some((idx, c)) => Err(AocError::parse(idx + 1, format!("expected a bracket, got '{}'", c))),
+        None => Ok(line.to_owned()),
+    })
 }
 
-fn parse_input(input: &str) -> Vec<InputType> {
-    input.lines()
-        .map(|line| line.to_owned())
-        .collect::<Vec<_>>()
-}
+pub(crate) struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<InputType>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart 1 {}\n\tPart 2 {}", DAY, part1(&input), part2(&input));
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        part2(input).map(Answer::from)
+    }
 }
 
 fn compute_error_score(error: char) -> u64 {
@@ -49,22 +59,19 @@ fn is_matching(current: char, last: char) -> bool {
 
 enum ParsingError {
     UnclosedParenthesis(char),
-    UnexpectedToken(char),
 }
 
-fn parse_line(line: &String) -> Result<Vec<char>, ParsingError> {
+fn parse_line(line: &String) -> std::result::Result<Vec<char>, ParsingError> {
     let mut stack = Vec::new();
     for c in line.chars() {
         match c {
             '(' | '[' | '{' | '<' => stack.push(c),
-            ')' | ']' | '}' | '>' => {
-                if let Some(popped) = stack.pop() {
-                    if !is_matching(c, popped) {
-                        return Err(ParsingError::UnclosedParenthesis(c));
-                    }
-                }
+            // Closing a chunk that was never opened corrupts the line as well
+            ')' | ']' | '}' | '>' => match stack.pop() {
+                Some(popped) if is_matching(c, popped) => (),
+                _ => return Err(ParsingError::UnclosedParenthesis(c)),
             },
-            _ => return Err(ParsingError::UnexpectedToken(c)),
+            _ => unreachable!("parse_input only lets brackets through"),
         }
     }
     Ok(stack)
@@ -76,7 +83,6 @@ fn parse_lines(input: &Vec<InputType>) -> (u64, Vec<Vec<char>>) {
     for line in input {
         match parse_line(line) {
             Err(ParsingError::UnclosedParenthesis(c)) => high_score += compute_error_score(c),
-            Err(ParsingError::UnexpectedToken(c)) => panic!("Unexpected Token encountered: {}", c),
             Ok(stack) => stacks.push(stack),
         }
     }
@@ -89,15 +95,19 @@ fn part1(input: &Vec<InputType>) -> u64 {
     high_score
 }
 
-fn part2(input: &Vec<InputType>) -> u64 {
+fn part2(input: &Vec<InputType>) -> Result<u64> {
     let (_, stacks) = parse_lines(input);
     let mut line_scores: Vec<u64> = stacks.iter()
-        .map(|stack| { stack.iter().rev()
-            .fold(0, |line_score , &c|  { line_score * 5 + compute_correction_score(c) })})
-        .collect();
+        .map(|stack| stack.iter().rev()
+            .try_fold(0_u64, |line_score, &c| line_score.checked_mul(5)?.checked_add(compute_correction_score(c)))
+            .ok_or_else(|| AocError::unsolvable("a completion score does not fit into 64 bits")))
+        .collect::<Result<_>>()?;
+    if line_scores.is_empty() {
+        return Err(AocError::unsolvable("every line is corrupted, there is nothing to complete"));
+    }
     line_scores.sort();
     let len = line_scores.len();
-    line_scores[len / 2]
+    Ok(line_scores[len / 2])
 }
 
 #[cfg(test)]
@@ -105,14 +115,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn day10_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(392043, part1(&input));
-    }
-
-    #[test]
-    fn day10_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(1605968119, part2(&input));
+    fn day10_rejects_what_it_cannot_score() {
+        assert_eq!(3, part1(&parse_input(")\n[]\n").unwrap()));
+        let input = parse_input(&"(".repeat(30)).unwrap();
+        assert!(matches!(part2(&input), Err(AocError::Unsolvable(_))));
     }
 }
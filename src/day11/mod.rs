@@ -1,69 +1,87 @@
-use aoc_downloader::download_day;
+use std::iter;
 
-const DAY: u32 = 11;
-type InputType = Vec<u32>;
-type Coordinate = (usize, usize);
+use crate::error::{AocError, Result};
+use crate::utils::{grid::{Grid, Position}, image::{self, GOLD}, render::{Colour, Renderer}};
+use crate::solution::{Answer, Frames, Images, Solution};
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
-}
+pub(crate) const DAY: u32 = 11;
+type InputType = Grid<u32>;
 
-fn parse_input(input: &str) -> Vec<InputType> {
-    input.lines()
-        .map(|line| line.chars().
-            map(|c| c.to_digit(10).unwrap())
-            .collect::<Vec<u32>>())
-        .collect::<Vec<_>>()
+fn parse_input(input: &str) -> Result<InputType> {
+    Grid::parse_digits(input)
 }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart 1 {}\n\tPart 2 {}", DAY, part1(&input), part2(&input));
-}
+pub(crate) struct Day11;
 
-fn get_neighbours(current_pos: Coordinate, input: &Vec<InputType>) -> Vec<Coordinate> {
-    lazy_static!{
-        static ref OFFSETS: Vec<(isize, isize)> = vec![
-            (0, 1), (1, 1), (1, 0), (1, -1), (0, -1), (-1, -1), (-1, 0), (-1, 1)
-        ];
+impl Solution for Day11 {
+    type Input = InputType;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
     }
-    let max_x = input[0].len() as isize;
-    let max_y = input.len() as isize;
-    let mut neighbours = vec![];
-    for offset in OFFSETS.iter() {
-        let new_pos = (current_pos.0 as isize + offset.0,
-            current_pos.1 as isize + offset.1);
-        if new_pos.0 < 0 || new_pos.0 >= max_x || new_pos.1 < 0 || new_pos.1 >= max_y {
-            continue;
-        }
-        neighbours.push((new_pos.0 as usize, new_pos.1 as usize));
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        part2(input).map(Answer::from)
+    }
+
+    // Octopuses that just flashed light up, until all of them flash together or MAX_ROUNDS steps have passed
+    fn frames(&self, mut input: Self::Input) -> Option<Frames> {
+        let render = |octopuses: &InputType| Renderer::new(|&energy: &u32| char::from_digit(energy, 10).unwrap_or('*'))
+            .colour(|&energy| (energy == 0).then_some(Colour::Yellow))
+            .grid(octopuses);
+        let first = format!("Step 0\n{}", render(&input));
+        let mut step = 0;
+        let mut synchronised = false;
+        Some(Box::new(iter::once(first).chain(iter::from_fn(move || {
+            if synchronised || step == MAX_ROUNDS {
+                return None;
+            }
+            step += 1;
+            let flashed = cycle_of_life(&mut input);
+            synchronised = flashed == input.cells().len();
+            Some(format!("Step {}, {} flashed\n{}", step, flashed, render(&input)))
+        }))))
     }
 
-    neighbours
+    // Same steps as the frames
+    fn images(&self, mut input: Self::Input) -> Option<Images> {
+        let paint = |octopuses: &InputType| octopuses.map(|&energy| if energy == 0 { GOLD } else { image::grey(energy, 12) });
+        let first = paint(&input);
+        let mut step = 0;
+        let mut synchronised = false;
+        Some(Box::new(iter::once(first).chain(iter::from_fn(move || {
+            if synchronised || step == MAX_ROUNDS {
+                return None;
+            }
+            step += 1;
+            synchronised = cycle_of_life(&mut input) == input.cells().len();
+            Some(paint(&input))
+        }))))
+    }
 }
 
-fn find_initial_flashers(input: &mut Vec<InputType>) -> Vec<Coordinate> {
+fn find_initial_flashers(input: &mut InputType) -> Vec<Position> {
     let mut flashers = Vec::new();
-    for (y, row) in input.clone().iter().enumerate() {
-        for (x, _) in row.iter().enumerate() {
-            input[y][x] += 1;
-            if input[y][x] > 9 {
-                flashers.push((y, x));
-            }
+    for (position, energy) in input.iter_mut() {
+        *energy += 1;
+        if *energy > 9 {
+            flashers.push(position);
         }
     }
     flashers
 }
 
-fn get_all_flashers_this_cycle(input: &mut Vec<InputType>, flashers: &mut Vec<Coordinate>) -> Vec<Coordinate> {
+fn get_all_flashers_this_cycle(input: &mut InputType, flashers: &mut Vec<Position>) -> Vec<Position> {
     let mut flashed = Vec::new();
     while let Some(flasher) = flashers.pop() {
-        let neighbours = get_neighbours(flasher, &input);
+        let neighbours: Vec<_> = input.neighbours8(flasher).map(|(neighbour, _)| neighbour).collect();
         for neighbour in neighbours {
-            input[neighbour.0][neighbour.1] += 1;
-            if input[neighbour.0][neighbour.1] == 10 {
+            input[neighbour] += 1;
+            if input[neighbour] == 10 {
                 flashers.push(neighbour);
             }
         }
@@ -72,17 +90,17 @@ fn get_all_flashers_this_cycle(input: &mut Vec<InputType>, flashers: &mut Vec<Co
     flashed
 }
 
-fn cycle_of_life(input: &mut Vec<InputType>) -> usize {
+fn cycle_of_life(input: &mut InputType) -> usize {
     let mut flashers = find_initial_flashers(input);
     let flashed = get_all_flashers_this_cycle(input, &mut flashers);
 
     for flasher in &flashed {
-        input[flasher.0][flasher.1] = 0;
+        input[*flasher] = 0;
     }
     flashed.len()
 }
 
-fn part1(input: &Vec<InputType>) -> usize {
+fn part1(input: &InputType) -> usize {
     let mut input = input.clone();
     let mut flashes = 0;
     let rounds = 100;
@@ -92,9 +110,12 @@ fn part1(input: &Vec<InputType>) -> usize {
     flashes
 }
 
-fn part2(input: &Vec<InputType>) -> u64 {
+// Far more than any puzzle input needs, some grids never flash all at once
+const MAX_ROUNDS: u64 = 100_000;
+
+fn part2(input: &InputType) -> Result<u64> {
     let mut input = input.clone();
-    let octopuses_max = input.len() * input[0].len();
+    let octopuses_max = input.cells().len();
     let mut rounds = 0;
     loop {
         let flashed_this_cycle = cycle_of_life(&mut input);
@@ -102,23 +123,30 @@ fn part2(input: &Vec<InputType>) -> u64 {
         if octopuses_max == flashed_this_cycle {
             break;
         }
+        if rounds == MAX_ROUNDS {
+            return Err(AocError::unsolvable(format!("the octopuses do not flash at once within {} steps", MAX_ROUNDS)));
+        }
     }
-    rounds
+    Ok(rounds)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // These four never flash at once
+    const OUT_OF_SYNC: &str = "17\n62\n";
+
     #[test]
-    fn day11_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(1739, part1(&input));
+    fn day11_frames_stop_without_synchronising() {
+        let input = parse_input(OUT_OF_SYNC).unwrap();
+        assert!(part2(&input).is_err());
+        assert_eq!(MAX_ROUNDS as usize + 1, Day11.frames(input).unwrap().count());
     }
 
     #[test]
-    fn day11_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(324, part2(&input));
+    fn day11_images_stop_without_synchronising() {
+        let input = parse_input(OUT_OF_SYNC).unwrap();
+        assert_eq!(MAX_ROUNDS as usize + 1, Day11.images(input).unwrap().count());
     }
 }
@@ -1,7 +1,9 @@
-use aoc_downloader::download_day;
-use regex::Regex;
 use std::{str::FromStr, collections::HashMap};
 
+use crate::error::{AocError, Result};
+use crate::utils::parse;
+use crate::solution::{Answer, Solution};
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 enum Node {
     Upper(u32),
@@ -11,9 +13,12 @@ enum Node {
 }
 
 impl FromStr for Node {
-    type Err = std::io::Error;
+    type Err = AocError;
 
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
+    fn from_str(input: &str) -> Result<Self> {
+        if input.is_empty() || !input.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(AocError::parse(1, format!("expected a cave name, got '{}'", input)));
+        }
         let node = match input {
             "start" => Node::Start,
             "end" => Node::End,
@@ -28,50 +33,53 @@ impl FromStr for Node {
 }
 
 #[derive(Debug)]
-struct Edge {
+pub(crate) struct Edge {
     start: Node,
     end: Node,
 }
 
 impl FromStr for Edge {
-    type Err = std::io::Error;
+    type Err = AocError;
 
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(r"(.+)-(.+)").unwrap();
-        }
+    fn from_str(input: &str) -> Result<Self> {
+        let (start, end) = input.split_once('-')
+            .ok_or_else(|| AocError::parse(1, format!("expected '<cave>-<cave>', got '{}'", input)))?;
+        let node = |name, column| Node::from_str(name).map_err(|error: AocError| error.at_column(column));
 
-        Ok(RE.captures(input).and_then(|captured| {
-            Some(Edge {
-                start: Node::from_str(&captured[1]).unwrap(),
-                end: Node::from_str(&captured[2]).unwrap(),
-            })
-        }).unwrap())
+        Ok(Edge {
+            start: node(start, 1)?,
+            end: node(end, start.len() + 2)?,
+        })
     }
 }
 
-const DAY: u32 = 12;
+pub(crate) const DAY: u32 = 12;
 type InputType = Edge;
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
-}
-
-fn parse_input(input: &str) -> Vec<InputType> {
-    let mut input = input.lines()
-        .map(|line| Edge::from_str(line).unwrap())
-        .collect::<Vec<_>>();
+fn parse_input(input: &str) -> Result<Vec<InputType>> {
+    let mut input = parse::lines(input, Edge::from_str)?;
     input.append(&mut input.iter()
         .map(|edge| Edge { start: edge.end, end: edge.start})
         .collect::<Vec<_>>());
-    input
+    Ok(input)
 }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart 1 {}\n\tPart 2 {}", DAY, part1(&input), part2(&input));
+pub(crate) struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<InputType>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
 }
 
 fn visit_cave_only(how_often: usize, visited: &HashMap<Node, usize>) -> bool {
@@ -131,20 +139,3 @@ fn part1(input: &Vec<InputType>) -> usize {
 fn part2(input: &Vec<InputType>) -> usize {
     modified_dfs(Node::Start, input, &vec![], &HashMap::new(), 2).len()
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn day12_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(4885, part1(&input));
-    }
-
-    #[test]
-    fn day12_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(117095, part2(&input));
-    }
-}
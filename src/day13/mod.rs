@@ -1,121 +1,111 @@
-use aoc_downloader::download_day;
-use regex::Regex;
+use std::iter;
 
-const DAY: u32 = 13;
-type InputType = String;
+use regex::Regex;
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
-}
+use crate::error::{AocError, Result};
+use crate::utils::{parse, coordinates::Vector, image::{NIGHT, WHITE}, ocr, sparse::{Point, SparseGrid}};
+use crate::solution::{Answer, Images, Solution};
 
-fn parse_input(input: &str) -> Vec<InputType> {
-    input.lines()
-        .filter(|line| *line != "")
-        .map(|line| line.to_string())
-        .collect::<Vec<_>>()
-}
+pub(crate) const DAY: u32 = 13;
+type InputType = Instructions;
+type Paper = SparseGrid<bool>;
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart 1 {}\n\tPart 2:", DAY, part1(&input));
-    part2(&&input);
+pub(crate) struct Instructions {
+    dots: Vec<Point>,
+    // Axis of the Point to fold, 0 for x and 1 for y, and where the line is
+    folds: Vec<(usize, isize)>,
 }
 
-fn print_paper(input: &Vec<InputType>) -> (Vec<Vec<usize>>, Vec<Vec<usize>>) {
+fn parse_input(input: &str) -> Result<InputType> {
     lazy_static! {
-        static ref RE: Regex = Regex::new(r"fold along (.)=(\d+)").unwrap();
+        static ref RE: Regex = Regex::new(r"^fold along ([xy])=(\d+)$").unwrap();
     }
 
-    let mut paper = vec![vec![0; 1500]; 1500];
-    let mut folding_instructions = Vec::new();
-    let mut max_x = 0;
-    let mut max_y = 0;
-    for line in input {
-        if !line.starts_with("fold along") {
-            let coords: Vec<usize> = line.split(",")
-                .map(|number| number.parse::<usize>().unwrap())
-                .collect();
-            max_y = std::cmp::max(max_y, coords[1]);
-            max_x = std::cmp::max(max_x, coords[0]);
-            paper[coords[1]][coords[0]] = 1;
+    let mut dots = Vec::new();
+    let mut folds = Vec::new();
+    parse::lines(input, |line| {
+        if line.starts_with("fold along") {
+            let captured = parse::captures(&RE, line, "'fold along x=<n>' or 'fold along y=<n>'")?;
+            let axis = if &captured[1] == "x" { 0 } else { 1 };
+            folds.push((axis, parse::capture(&captured, 2)?));
         } else {
-            RE.captures(line).and_then(|captured| {
-                let value = captured[2].parse::<usize>().unwrap();
-                let idx = if captured[1] == *"y" { 0 } else { 1 };
-                folding_instructions.push(vec![idx, value]);
-            Some(true)
-            }).unwrap();
+            let (x, y) = line.split_once(',')
+                .ok_or_else(|| AocError::parse(1, format!("expected 'x,y', got '{}'", line)))?;
+            dots.push(Vector::from((parse::number(x, 1)?, parse::number(y, x.len() + 2)?)));
         }
+        Ok(())
+    })?;
+
+    Ok(Instructions { dots, folds })
+}
+
+pub(crate) struct Day13;
+
+impl Solution for Day13 {
+    type Input = InputType;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
     }
-    paper.drain(max_y+1..);
-    for row in &mut paper {
-        row.drain(max_x+1..);
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        part1(input).map(Answer::from)
     }
 
-    (paper, folding_instructions)
-}
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        part2(input).map(Answer::from)
+    }
 
-fn part1(input: &Vec<InputType>) -> u64 {
-    let (mut paper, folding_instructions) = print_paper(input);
+    // The paper before the first fold and after every one of them
+    fn images(&self, input: Self::Input) -> Option<Images> {
+        let paint = |paper: &Paper| paper.to_grid().map(|&dot| if dot { WHITE } else { NIGHT });
+        let paper = print_paper(&input);
+        let first = paint(&paper);
+        Some(Box::new(iter::once(first).chain(input.folds.into_iter().scan(paper, move |paper, fold| {
+            *paper = fold_it(fold, paper);
+            Some(paint(paper))
+        }))))
+    }
+}
 
-    fold_it(folding_instructions[0].clone(), &mut paper);
-    let mut count = 0;
-    for row in paper {
-        for spot in row {
-            if spot > 0 {
-                count += 1;
-            }
-        }
+fn print_paper(input: &InputType) -> Paper {
+    let mut paper = Paper::new(false);
+    for &dot in &input.dots {
+        paper.insert(dot, true);
     }
-    count
+    paper
 }
 
-fn fold_it(fold: Vec<usize>, paper: &mut Vec<Vec<usize>>) {
-    if fold[0] == 0 {
-        paper.remove(fold[1]);
-        let mut lower_half: Vec<Vec<usize>> = paper.drain(fold[1]..).collect();
-        lower_half.reverse();
-        for (y, row) in lower_half.iter().enumerate() {
-            for (x, spot) in row.iter().enumerate() {
-                paper[y][x] += spot;
-            }
-        }
-    } else {
-        let mut right_half = vec![];
-        for row in paper.into_iter() {
-            row.remove(fold[1]);
-            let right_row: Vec<usize> = row.drain(fold[1]..).collect();
-            right_half.push(right_row);
-        }
-        for row in &mut right_half {
-            row.reverse();
+fn part1(input: &InputType) -> Result<u64> {
+    let paper = print_paper(input);
+
+    let &fold = input.folds.first().ok_or_else(|| AocError::unsolvable("there are no folding instructions"))?;
+    Ok(fold_it(fold, &paper).len() as u64)
+}
+
+// Dots past the line are mirrored onto the other side, the paper is unbounded so either half may be the larger one
+fn fold_it((axis, line): (usize, isize), paper: &Paper) -> Paper {
+    let mut folded = Paper::new(false);
+    for (&dot, _) in paper {
+        let mut dot = dot;
+        if dot[axis] > line {
+            dot[axis] = 2 * line - dot[axis];
         }
-        for (y, row) in right_half.iter().enumerate() {
-            for (x, spot) in row.iter().enumerate() {
-                paper[y][x] += spot;
-            }
+        // Dots on the line itself vanish in the crease
+        if dot[axis] != line {
+            folded.insert(dot, true);
         }
     }
+    folded
 }
 
-fn part2(input: &Vec<InputType>) -> u64 {
-    let (mut paper, folding_instructions) = print_paper(input);
-    for fold in folding_instructions {
-        fold_it(fold, &mut paper);
-    }
-    for row in paper {
-        for dot in row {
-            if dot > 2 {
-                print!("#");
-            } else {
-                print!(" ");
-            }
-        }
-        println!("");
+// The dots left over spell out the code
+fn part2(input: &InputType) -> Result<String> {
+    let mut paper = print_paper(input);
+    for &fold in &input.folds {
+        paper = fold_it(fold, &paper);
     }
-    0
+    ocr::read(&paper.to_grid())
 }
 
 #[cfg(test)]
@@ -123,14 +113,18 @@ mod tests {
     use super::*;
 
     #[test]
-    fn day13_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(682, part1(&input));
-    }
-
-    #[test]
-    fn day13_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(0, part2(&input));
+    fn day13_part2_reads_the_folded_code() {
+        // Left half of the dots as they are, the right half mirrored below the fold
+        let letters = ocr::draw("HELP").unwrap();
+        let line = letters.row_count();
+        let dots: String = letters.iter()
+            .filter(|(_, &dot)| dot)
+            .map(|((row, column), _)| {
+                let row = if column < letters.column_count() / 2 { row } else { 2 * line - row };
+                format!("{},{}\n", column, row)
+            })
+            .collect();
+        let input = parse_input(&format!("{}\nfold along y={}\n", dots, line)).unwrap();
+        assert_eq!("HELP", part2(&input).unwrap());
     }
 }
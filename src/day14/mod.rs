@@ -1,51 +1,76 @@
-use aoc_downloader::download_day;
 use regex::Regex;
 use std::{collections::HashMap, hash::Hash};
 
-const DAY: u32 = 14;
-type InputType = (Vec<char>, HashMap<String, char>);
+use crate::error::{AocError, Result};
+use crate::utils::parse;
+use crate::solution::{Answer, Solution};
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
-}
+pub(crate) const DAY: u32 = 14;
+type InputType = (Vec<char>, HashMap<String, char>);
 
-fn parse_input(input: &str) -> InputType {
+fn parse_input(input: &str) -> Result<InputType> {
     lazy_static! {
-        static ref RE: Regex = Regex::new(r"(.+) -> (.)").unwrap();
+        static ref RE: Regex = Regex::new(r"^(..) -> (.)$").unwrap();
     }
-    let input: Vec<String> = input.lines()
-        .filter(|line| *line != "")
-        .map(|line| line.to_string())
-        .collect();
-    let template: Vec<char> = input[0].chars().collect();
+    let mut lines = input.lines()
+        .enumerate()
+        .filter(|(_, line)| *line != "");
+    let template: Vec<char> = match lines.next() {
+        Some((_, line)) => line.chars().collect(),
+        None => return Err(AocError::parse(1, "expected the polymer template")),
+    };
     let mut translations: HashMap<String, char> = HashMap::new();
-    for line in input[1..].into_iter() {
-        RE.captures(line).and_then(|captured| {
-            translations.insert(
-                captured[1].to_string(),
-                captured[2].chars().nth(0).unwrap());
-            Some(0)
-        }).unwrap();
+    for (idx, line) in lines {
+        let captured = parse::captures(&RE, line, "'AB -> C'").map_err(|error| error.at_line(idx + 1))?;
+        translations.insert(
+            captured[1].to_string(),
+            captured[2].chars().nth(0).unwrap());
     }
 
-    (template, translations)
+    Ok((template, translations))
 }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart 1 {}\n\tPart 2: {}", DAY, part1(input.clone()), part2(input.clone()));
+fn insertion(translations: &HashMap<String, char>, pair: &String) -> Result<char> {
+    translations.get(pair)
+        .copied()
+        .ok_or_else(|| AocError::unsolvable(format!("there is no insertion rule for {}", pair)))
 }
 
-fn part1(input: InputType) -> u64 {
+pub(crate) struct Day14;
+
+impl Solution for Day14 {
+    type Input = InputType;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        part1(input.clone()).map(Answer::from)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        part2(input.clone()).map(Answer::from)
+    }
+}
+
+fn part1(input: InputType) -> Result<u64> {
+    expand(input, 10)
+}
+
+fn part2(input: InputType) -> Result<u64> {
+    count_pairs(input, 40)
+}
+
+// Builds the whole polymer, which doubles in length with every step
+fn expand(input: InputType, steps: usize) -> Result<u64> {
     let (mut template, translations) = input;
-    for _ in 0..10 {
+    for _ in 0..steps {
         let mut next_template = Vec::new();
         next_template.push(template[0]);
         for pair in template.windows(2) {
             let current: String = pair.into_iter().collect();
-            let new_char = translations[&current];
+            let new_char = insertion(&translations, &current)?;
             next_template.push(new_char);
             next_template.push(pair[1]);
         }
@@ -61,10 +86,11 @@ fn part1(input: InputType) -> u64 {
         max = std::cmp::max(max, count);
         min = std::cmp::min(min, count);
     }
-    max - min
+    Ok(max - min)
 }
 
-fn part2(input: InputType) -> u64 {
+// Only counts how often every pair occurs, so the polymer can grow as long as it likes
+fn count_pairs(input: InputType, steps: usize) -> Result<u64> {
     let (template, translations) = input;
 
     let mut occurences: HashMap<String, u64> = HashMap::new();
@@ -72,13 +98,13 @@ fn part2(input: InputType) -> u64 {
         add_or_insert(pair.iter().collect::<String>(), 1, &mut occurences);
     }
 
-    for _ in 0..40 {
+    for _ in 0..steps {
         let mut next_occurences: HashMap<String, u64> = HashMap::new();
         for (pair, pair_count) in &occurences {
 
             let first_element = pair.chars().nth(0).unwrap();
             let second_element = pair.chars().nth(1).unwrap();
-            let new_element = translations[pair];
+            let new_element = insertion(&translations, pair)?;
 
             add_or_insert(vec![first_element, new_element].iter().collect::<String>(),
                 *pair_count, &mut next_occurences);
@@ -105,7 +131,7 @@ fn part2(input: InputType) -> u64 {
         max = std::cmp::max(max, count);
         min = std::cmp::min(min, count);
     }
-    max - min
+    Ok(max - min)
 }
 
 fn add_or_insert<K: Eq + Hash, V: Copy + std::ops::AddAssign>(key: K, value: V, map: &mut HashMap<K, V>) {
@@ -117,16 +143,22 @@ fn add_or_insert<K: Eq + Hash, V: Copy + std::ops::AddAssign>(key: K, value: V,
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::gen;
 
     #[test]
-    fn day14_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(3406, part1(input));
-    }
-
-    #[test]
-    fn day14_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(3941782230241, part2(input));
+    fn day14_pair_counts_match_expansion() {
+        const ELEMENTS: [char; 4] = ['B', 'C', 'H', 'N'];
+        gen::check(200, |rng| {
+            let template: Vec<char> = (0..rng.range(1..=6)).map(|_| *rng.pick(&ELEMENTS)).collect();
+            let mut translations = HashMap::new();
+            for a in ELEMENTS {
+                for b in ELEMENTS {
+                    translations.insert([a, b].iter().collect(), *rng.pick(&ELEMENTS));
+                }
+            }
+            let steps = rng.below(11);
+            let input = (template, translations);
+            assert_eq!(expand(input.clone(), steps).unwrap(), count_pairs(input.clone(), steps).unwrap(), "{:?} after {} steps", input, steps);
+        });
     }
 }
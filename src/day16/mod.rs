@@ -1,22 +1,28 @@
-use aoc_downloader::download_day;
+use crate::error::{AocError, Result};
+use crate::solution::{Answer, Solution};
 
-const DAY: u32 = 16;
-type InputType = Vec<char>;
+pub(crate) const DAY: u32 = 16;
+type InputType = Packet;
+type Bits = Vec<char>;
+// Decoding errors only know what went wrong, parse_input knows where
+type Decoded<T> = std::result::Result<T, &'static str>;
+
+const TRUNCATED: &str = "transmission ends in the middle of a packet";
 
 #[derive(Debug)]
-enum Packet{
+pub(crate) enum Packet{
     Lit(Literal),
     Op(Operator),
 }
 
 #[derive(Debug)]
-struct Literal {
+pub(crate) struct Literal {
     packet_version: u64,
     value: u64,
 }
 
 #[derive(Debug)]
-struct Operator {
+pub(crate) struct Operator {
     packet_version: u64,
     type_id: u64,
     subpackets: Vec<Packet>,
@@ -28,107 +34,135 @@ enum LengthValue {
     SubPackets(usize),
 }
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
-}
-
-fn parse_input(input: &str) -> InputType {
-    input.chars()
-        .filter(|&c| c != '\n')
-        .map(|c| c.to_digit(16).unwrap() as u64)
-        .map(|number| format!("{:04b}", number))
+fn parse_input(input: &str) -> Result<InputType> {
+    let line = input.trim_end();
+    if let Some((idx, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+        return Err(AocError::parse(idx + 1, format!("expected a hexadecimal digit, got '{}'", c)));
+    }
+    let mut bits: Bits = line.chars()
+        .map(|c| format!("{:04b}", c.to_digit(16).unwrap()))
         .collect::<String>()
         .chars()
         .rev()
-        .collect()
+        .collect();
+    let total = bits.len();
+    match parse_packet(&mut bits) {
+        Ok((packet, _)) => Ok(packet),
+        Err(message) => Err(AocError::parse((total - bits.len()) / 4 + 1, message)),
+    }
 }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart 1 {}\n\tPart 2: {}", DAY, part1(&input), part2(&input));
+pub(crate) struct Day16;
+
+impl Solution for Day16 {
+    type Input = InputType;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        part2(input).map(Answer::from)
+    }
 }
 
-fn get_value_by_length(input: &mut InputType, length: usize) -> u64 {
+fn get_value_by_length(input: &mut Bits, length: usize) -> Decoded<u64> {
     let len = input.len();
+    if len < length {
+        return Err(TRUNCATED);
+    }
     let buffer: String = input.drain(len-length..).rev().collect();
-    u64::from_str_radix(&buffer, 2).unwrap()
+    Ok(u64::from_str_radix(&buffer, 2).unwrap())
 }
 
-fn get_value(input: &mut InputType) -> (u64, usize) {
+fn get_value(input: &mut Bits) -> Decoded<(u64, usize)> {
     let mut parsed = 0;
     let mut buffer = vec![];
     loop {
-        let end = input.pop().unwrap();
+        let end = input.pop().ok_or(TRUNCATED)?;
         let len = input.len();
+        if len < 4 {
+            return Err(TRUNCATED);
+        }
         buffer.append(&mut input.drain(len-4..).rev().collect());
         parsed += 5;
         if end == '0' {
             break;
         }
     }
-    (u64::from_str_radix(&buffer.iter().collect::<String>(), 2).unwrap(), parsed)
+    let value = u64::from_str_radix(&buffer.iter().collect::<String>(), 2)
+        .map_err(|_| "literal value does not fit into 64 bits")?;
+    Ok((value, parsed))
 }
 
-fn get_length_type(input: &mut InputType) -> (LengthValue, usize) {
-    if input.pop().unwrap() == '0' {
-        let length = get_value_by_length(input, 15);
-        (LengthValue::TotalLength(length as usize), 16)
+fn get_length_type(input: &mut Bits) -> Decoded<(LengthValue, usize)> {
+    if input.pop().ok_or(TRUNCATED)? == '0' {
+        let length = get_value_by_length(input, 15)?;
+        Ok((LengthValue::TotalLength(length as usize), 16))
     } else {
-        let length = get_value_by_length(input, 11);
-        (LengthValue::SubPackets(length as usize), 12)
+        let length = get_value_by_length(input, 11)?;
+        Ok((LengthValue::SubPackets(length as usize), 12))
     }
 }
 
-fn parse_by_length(input: &mut InputType, len: usize) -> (Vec<Packet>, usize) {
+fn parse_by_length(input: &mut Bits, len: usize) -> Decoded<(Vec<Packet>, usize)> {
     let mut subpackets = Vec::new();
     let mut total_parsed = 0;
     while len != total_parsed {
-        let (packet, parsed) = parse_packet(input);
+        if total_parsed > len {
+            return Err("sub-packets are longer than their operator announced");
+        }
+        let (packet, parsed) = parse_packet(input)?;
         total_parsed += parsed;
         subpackets.push(packet);
     }
 
-    (subpackets, total_parsed)
+    Ok((subpackets, total_parsed))
 }
 
-fn parse_by_number(input: &mut InputType, mut len: usize) -> (Vec<Packet>, usize) {
+fn parse_by_number(input: &mut Bits, mut len: usize) -> Decoded<(Vec<Packet>, usize)> {
     let mut subpackets = Vec::new();
     let mut total_parsed = 0;
     while len != 0 {
-        let (packet, parsed) = parse_packet(input);
+        let (packet, parsed) = parse_packet(input)?;
         len -= 1;
         total_parsed += parsed;
         subpackets.push(packet);
     }
 
-    (subpackets, total_parsed)
+    Ok((subpackets, total_parsed))
 }
 
-fn parse_packet(input: &mut InputType) -> (Packet, usize) {
+fn parse_packet(input: &mut Bits) -> Decoded<(Packet, usize)> {
     let mut parsed = 0;
 
-    let packet_version = get_value_by_length(input, 3);
+    let packet_version = get_value_by_length(input, 3)?;
     parsed += 3;
-    let type_id = get_value_by_length(input, 3);
+    let type_id = get_value_by_length(input, 3)?;
     parsed += 3;
 
     if type_id == 4 {
-        let (value, size) = get_value(input);
+        let (value, size) = get_value(input)?;
         parsed += size;
-        (Packet::Lit(Literal { packet_version, value }), parsed)
+        Ok((Packet::Lit(Literal { packet_version, value }), parsed))
     } else {
-        let (length_type, size) = get_length_type(input);
+        let (length_type, size) = get_length_type(input)?;
         parsed += size;
         let output;
         match length_type {
-            LengthValue::TotalLength(len) => output = parse_by_length(input, len),
-            LengthValue::SubPackets(len) => output = parse_by_number(input, len),
+            LengthValue::TotalLength(len) => output = parse_by_length(input, len)?,
+            LengthValue::SubPackets(len) => output = parse_by_number(input, len)?,
         }
         let (subpackets, parsed_) = output;
         parsed += parsed_;
-        (Packet::Op(Operator { packet_version, type_id, subpackets}), parsed)
+        if (5..=7).contains(&type_id) && subpackets.len() != 2 {
+            return Err("comparison packets need exactly two sub-packets");
+        }
+        Ok((Packet::Op(Operator { packet_version, type_id, subpackets}), parsed))
     }
 }
 
@@ -145,46 +179,32 @@ fn get_version_number(packet: &Packet) -> u64 {
     }
 }
 
-fn calculate(packet: &Packet) -> u64 {
+fn calculate(packet: &Packet) -> Result<u64> {
     match packet {
-        Packet::Lit(lit) => lit.value,
+        Packet::Lit(lit) => Ok(lit.value),
         Packet::Op(op) => {
-            match op.type_id {
-                0 => op.subpackets.iter().fold(0, |acc, pkg| acc + calculate(pkg)),
-                1 => op.subpackets.iter().fold(1, |acc, pkg| acc * calculate(pkg)),
-                2 => {
-                    let mut min = u64::MAX;
-                    for package in &op.subpackets {
-                        min = std::cmp::min(min, calculate(package));
-                    }
-                    min
-                },
-                3 => {
-                    let mut max = 0;
-                    for package in &op.subpackets {
-                        max = std::cmp::max(max, calculate(package));
-                    }
-                    max
-                },
-                5 => if calculate(&op.subpackets[0]) > calculate(&op.subpackets[1]) { 1 } else { 0 },
-                6 => if calculate(&op.subpackets[0]) < calculate(&op.subpackets[1]) { 1 } else { 0 },
-                7 => if calculate(&op.subpackets[0]) == calculate(&op.subpackets[1]) { 1 } else { 0 },
-                _ => panic!(),
-            }
+            let values = op.subpackets.iter().map(calculate).collect::<Result<Vec<u64>>>()?;
+            let value = match op.type_id {
+                0 => values.iter().try_fold(0_u64, |sum, &value| sum.checked_add(value)),
+                1 => values.iter().try_fold(1_u64, |product, &value| product.checked_mul(value)),
+                2 => Some(values.iter().copied().min().unwrap_or(u64::MAX)),
+                3 => Some(values.iter().copied().max().unwrap_or(0)),
+                5 => Some((values[0] > values[1]) as u64),
+                6 => Some((values[0] < values[1]) as u64),
+                7 => Some((values[0] == values[1]) as u64),
+                _ => unreachable!("type 4 packets are literals"),
+            };
+            value.ok_or_else(|| AocError::unsolvable("the transmission evaluates to more than 64 bits"))
         }
     }
 }
 
 fn part1(input: &InputType) -> u64 {
-    let mut input = input.clone();
-    let (packet, _) = parse_packet(&mut input);
-    get_version_number(&packet)
+    get_version_number(input)
 }
 
-fn part2(input: &InputType) -> u64 {
-    let mut input = input.clone();
-    let (packet, _) = parse_packet(&mut input);
-    calculate(&packet)
+fn part2(input: &InputType) -> Result<u64> {
+    calculate(input)
 }
 
 #[cfg(test)]
@@ -192,14 +212,12 @@ mod tests {
     use super::*;
 
     #[test]
-    fn day16_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(873, part1(&input));
-    }
-
-    #[test]
-    fn day16_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(402817863665, part2(&input));
+    fn day16_reports_broken_transmissions() {
+        assert_eq!(16, part1(&parse_input("8A004A801A8002F478\n").unwrap()));
+        assert!(matches!(parse_input("8A004A80"), Err(AocError::Parse { line: 1, column: 7, .. })));
+        assert!(matches!(parse_input("8A0G"), Err(AocError::Parse { line: 1, column: 4, .. })));
+        // A sum of two literals of 2^63
+        let input = parse_input("020084C42108421084210842001310842108421084210800\n").unwrap();
+        assert!(matches!(part2(&input), Err(AocError::Unsolvable(_))));
     }
 }
@@ -1,17 +1,24 @@
-use aoc_downloader::download_day;
+use std::iter;
+
 use regex::Regex;
 
-const DAY: u32 = 17;
+use crate::error::Result;
+use crate::utils::{parse, coordinates::Vector, render::Renderer, sparse::{Point, SparseGrid}};
+use crate::solution::{Answer, Frames, Solution};
+
+pub(crate) const DAY: u32 = 17;
 type InputType = TargetArea;
-type Coords = (i64, i64);
+type Coords = Vector<i64, 2>;
 
-struct TargetArea {
-    x_start: i64,
-    x_end: i64,
-    y_start: i64,
-    y_end: i64,
+// Corners of the area, both of them inside
+pub(crate) struct TargetArea {
+    start: Coords,
+    end: Coords,
 }
 
+// Drag only slows the probe down horizontally until it stops, gravity keeps pulling
+const GRAVITY: Coords = Vector::new([0, -1]);
+
 #[derive(Debug)]
 pub struct Probe {
     position: Coords,
@@ -24,81 +31,121 @@ enum Failure {
 
 impl Probe {
     fn step(&mut self) {
-        self.position.0 += self.velocity.0;
-        self.position.1 += self.velocity.1;
+        self.position += self.velocity;
 
-        self.velocity.0 = std::cmp::max(self.velocity.0 - 1, 0);
-        self.velocity.1 -= 1;
+        let drag = Vector::new([-self.velocity.x().signum(), 0]);
+        self.velocity += drag + GRAVITY;
     }
 
-    fn simulate(&mut self, target: &TargetArea) -> Result<i64, Failure> {
+    fn simulate(&mut self, target: &TargetArea) -> std::result::Result<i64, Failure> {
         let mut max_height = 0;
         loop  {
             self.step();
-            max_height = std::cmp::max(self.position.1, max_height);
+            max_height = std::cmp::max(self.position.y(), max_height);
             if self.hit(&target) {
                 return Ok(max_height);
             }
-            if self.velocity.1 < 0 && self.position.1 < target.y_start {
+            if self.velocity.y() < 0 && self.position.y() < target.start.y() {
                 return Err(Failure::NotHit);
             }
         }
     }
 
     fn hit(&self, target: &TargetArea) -> bool {
-        target.x_start <= self.position.0 && self.position.0 <= target.x_end &&
-            target.y_start <= self.position.1 && self.position.1 <= target.y_end
+        target.start.x() <= self.position.x() && self.position.x() <= target.end.x() &&
+            target.start.y() <= self.position.y() && self.position.y() <= target.end.y()
     }
 }
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
+fn parse_input(input: &str) -> Result<InputType> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^target area: x=(-?\d+)\.\.(-?\d+), y=(-?\d+)\.\.(-?\d+)$").unwrap();
+    }
+    let line = input.trim_end();
+    let captured = parse::captures(&RE, line, "'target area: x=<from>..<to>, y=<from>..<to>'")?;
+    Ok(TargetArea {
+        start: Vector::from((parse::capture(&captured, 1)?, parse::capture(&captured, 3)?)),
+        end: Vector::from((parse::capture(&captured, 2)?, parse::capture(&captured, 4)?)),
+    })
 }
 
-fn parse_input(input: &str) -> InputType {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"target area: x=(-?\d+)..(-?\d+), y=(-?\d+)..(-?\d+)\n").unwrap();
+pub(crate) struct Day17;
+
+impl Solution for Day17 {
+    type Input = InputType;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
+
+    // The path of the highest shot, one step per frame, with y pointing up
+    fn frames(&self, input: Self::Input) -> Option<Frames> {
+        let (velocity, _) = highest_shot(&input)?;
+        let mut sky = SparseGrid::new(' ');
+        for y in input.start.y()..=input.end.y() {
+            for x in input.start.x()..=input.end.x() {
+                sky.insert(on_screen(Vector::from((x, y))), 'T');
+            }
+        }
+        sky.insert(Vector::zero(), 'S');
+        let mut probe = Probe { position: Vector::zero(), velocity };
+        let mut hit = false;
+        Some(Box::new(iter::from_fn(move || {
+            if hit {
+                return None;
+            }
+            probe.step();
+            hit = probe.hit(&input);
+            sky.insert(on_screen(probe.position), '#');
+            let render = Renderer::new(|&c: &char| c).sparse(&sky);
+            Some(format!("Position {:?}, velocity {:?}\n{}", probe.position.coordinates(), probe.velocity.coordinates(), render))
+        })))
     }
-    RE.captures(input).and_then(|captured| {
-        Some(TargetArea {
-            x_start: captured[1].parse::<i64>().unwrap(),
-            x_end: captured[2].parse::<i64>().unwrap(),
-            y_start: captured[3].parse::<i64>().unwrap(),
-            y_end: captured[4].parse::<i64>().unwrap(),
-        })
-    }).unwrap()
 }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart 1 {}\n\tPart 2: {}", DAY, part1(&input), part2(&input));
+// Rows grow downwards on screen
+fn on_screen(position: Coords) -> Point {
+    Vector::from((position.x() as isize, -position.y() as isize))
 }
 
-fn part1(input: &InputType) -> i64 {
-    let mut max_y = 0;
-    for x_vel in 0..input.x_end {
+// Starting velocity and top of the shot that hits the target after climbing the highest
+fn highest_shot(input: &InputType) -> Option<(Coords, i64)> {
+    let mut best = None;
+    for x_vel in 0..input.end.x() {
         for y_vel in 0..1000 {
             let mut probe = Probe {
-                position: (0, 0),
-                velocity: (x_vel, y_vel),
+                position: Vector::zero(),
+                velocity: Vector::from((x_vel, y_vel)),
             };
-            if let Ok(y) = probe.simulate(&input) {
-                max_y = std::cmp::max(max_y, y);
+            if let Ok(y) = probe.simulate(input) {
+                if best.is_none_or(|(_, max_y)| y > max_y) {
+                    best = Some((Vector::from((x_vel, y_vel)), y));
+                }
             }
         }
     }
-    max_y
+    best
+}
+
+fn part1(input: &InputType) -> i64 {
+    highest_shot(input).map_or(0, |(_, max_y)| max_y)
 }
 
 fn part2(input: &InputType) -> u64 {
     let mut works = 0;
-    for x_vel in 0..=input.x_end {
-        for y_vel in input.y_start..=1500 {
+    for x_vel in 0..=input.end.x() {
+        for y_vel in input.start.y()..=1500 {
             let mut probe = Probe {
-                position: (0, 0),
-                velocity: (x_vel, y_vel),
+                position: Vector::zero(),
+                velocity: Vector::from((x_vel, y_vel)),
             };
             if probe.simulate(input).is_ok() {
                 works += 1;
@@ -107,20 +154,3 @@ fn part2(input: &InputType) -> u64 {
     }
     works
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn day17_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(35511, part1(&input));
-    }
-
-    #[test]
-    fn day17_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(3282, part2(&input));
-    }
-}
@@ -2,13 +2,17 @@ use std::fmt;
 use num::Integer;
 use itertools::Itertools;
 
-use aoc_downloader::download_day;
+use crate::error::{AocError, Result};
+use crate::utils::parse;
+use crate::solution::{Answer, Solution};
 
-const DAY: u32 = 18;
+pub(crate) const DAY: u32 = 18;
 type InputType = Vec<Pair>;
+// Pair::from_str only knows what went wrong, parse_input knows where
+type Decoded<T> = std::result::Result<T, &'static str>;
 
 #[derive(Clone, PartialEq)]
-enum Pair {
+pub(crate) enum Pair {
     Number(u64),
     Pair(Box<Pair>, Box<Pair>),
 }
@@ -40,24 +44,24 @@ impl fmt::Debug for Pair {
 }
 
 impl Pair {
-    pub fn from_str(input: &mut String) -> Pair {
-        let left_start = input.pop().unwrap();
-        let left;
-        if '[' == left_start {
-            left = Self::from_str(input);
-        } else {
-            left = Self::Number(left_start.to_digit(10).unwrap() as u64);
+    pub fn from_str(input: &mut String) -> Decoded<Pair> {
+        let left = Self::element_from_str(input)?;
+        if input.pop() != Some(',') {
+            return Err("expected ',' between the elements of a pair");
         }
-        input.pop().unwrap(); // Discard delimiter
-        let right_start = input.pop().unwrap();
-        let right;
-        if '[' == right_start {
-            right = Self::from_str(input);
-        } else {
-            right = Self::Number(right_start.to_digit(10).unwrap() as u64);
+        let right = Self::element_from_str(input)?;
+        if input.pop() != Some(']') {
+            return Err("expected ']' after the second element of a pair");
+        }
+        Ok(Self::Pair(Box::new(left), Box::new(right)))
+    }
+
+    fn element_from_str(input: &mut String) -> Decoded<Pair> {
+        match input.pop() {
+            Some('[') => Self::from_str(input),
+            Some(digit) if digit.is_ascii_digit() => Ok(Self::Number(digit.to_digit(10).unwrap() as u64)),
+            _ => Err("expected a digit or '['"),
         }
-        input.pop().unwrap(); // Discard closing
-        Self::Pair(Box::new(left), Box::new(right))
     }
 
     pub fn get_magnitude(&self) -> u64 {
@@ -164,47 +168,71 @@ impl Pair {
     }
 }
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
+fn parse_input(input: &str) -> Result<InputType> {
+    parse::lines(input, |line| {
+        // Reducing only ever explodes pairs of two regular numbers, which needs reduced inputs.
+        // Checked before parsing, so that no line can nest deep enough to overflow the stack.
+        let mut depth = 0_usize;
+        for (idx, c) in line.char_indices() {
+            match c {
+                '[' => depth += 1,
+                ']' => depth = depth.saturating_sub(1),
+                _ => (),
+            }
+            if depth > 4 {
+                return Err(AocError::parse(idx + 1, "pairs can only be nested four deep"));
+            }
+        }
+        let mut input: String = line.chars().rev().collect();
+        let pair = match input.pop() {
+            Some('[') => Pair::from_str(&mut input),
+            _ => Err("expected '['"),
+        }.map_err(|message| AocError::parse(line.len() - input.len(), message))?;
+        if !input.is_empty() {
+            return Err(AocError::parse(line.len() - input.len() + 1, "expected the line to end after the pair"));
+        }
+        Ok(pair)
+    })
 }
 
-fn parse_input(input: &str) -> InputType {
-    input.lines()
-        .filter(|&line| line != "")
-        .map(|line| {
-            let input = line[1..].to_string();
-            Pair::from_str(&mut input.chars().rev().collect())
-        })
-        .collect()
-}
+pub(crate) struct Day18;
+
+impl Solution for Day18 {
+    type Input = InputType;
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart 1 {}\n\tPart 2: {}", DAY, part1(&input), part2(&input));
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        part2(input).map(Answer::from)
+    }
 }
 
-fn part1(input: &InputType) -> u64 {
+fn part1(input: &InputType) -> Result<u64> {
     let input = input.clone();
 
     input.into_iter()
         .reduce(|current, next| {
             (current + next).reduce()
         })
-        .unwrap()
-        .get_magnitude()
+        .map(|sum| sum.get_magnitude())
+        .ok_or_else(|| AocError::unsolvable("there are no numbers to add up"))
 }
 
-fn part2(input: &InputType) -> u64 {
+fn part2(input: &InputType) -> Result<u64> {
     let input = input.clone();
 
     input.into_iter()
         .permutations(2)
         .map(|permutations| (permutations[0].clone() + permutations[1].clone()).reduce())
         .max_by(|a, b| a.get_magnitude().cmp(&b.get_magnitude()))
-        .unwrap()
-        .get_magnitude()
+        .map(|sum| sum.get_magnitude())
+        .ok_or_else(|| AocError::unsolvable("it takes at least two numbers to add two of them"))
 }
 
 #[cfg(test)]
@@ -214,7 +242,7 @@ mod tests {
     #[test]
     fn from_str_works() {
         let input = "[[9,1],[1,9]]"[1..].to_string();
-        let pair = Pair::from_str(&mut input.chars().rev().collect());
+        let pair = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
 
         let expected_pair = Pair::Pair(
             Box::new(Pair::Pair(
@@ -233,7 +261,7 @@ mod tests {
     #[test]
     fn magnitude_example1() {
         let input = "[[9,1],[1,9]]"[1..].to_string();
-        let pair = Pair::from_str(&mut input.chars().rev().collect());
+        let pair = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
 
         assert_eq!(129, pair.get_magnitude());
     }
@@ -241,7 +269,7 @@ mod tests {
     #[test]
     fn magnitude_example2() {
         let input = "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"[1..].to_string();
-        let pair = Pair::from_str(&mut input.chars().rev().collect());
+        let pair = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
 
         assert_eq!(1384, pair.get_magnitude());
     }
@@ -249,7 +277,7 @@ mod tests {
     #[test]
     fn magnitude_example3() {
         let input = "[[[[1,1],[2,2]],[3,3]],[4,4]]"[1..].to_string();
-        let pair = Pair::from_str(&mut input.chars().rev().collect());
+        let pair = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
 
         assert_eq!(445, pair.get_magnitude());
     }
@@ -257,16 +285,15 @@ mod tests {
     #[test]
     fn example_1_works() {
         let input = "[[[[4,3],4],4],[7,[[8,4],9]]]"[1..].to_string();
-        let pair_a = Pair::from_str(&mut input.chars().rev().collect());
+        let pair_a = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
 
         let input = "[1,1]"[1..].to_string();
-        let pair_b = Pair::from_str(&mut input.chars().rev().collect());
+        let pair_b = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
 
         let mut pair = pair_a + pair_b;
 
-
         let input = "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"[1..].to_string();
-        let expected_pair = Pair::from_str(&mut input.chars().rev().collect());
+        let expected_pair = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
 
         let pair = pair.reduce();
         assert_eq!(expected_pair, pair);
@@ -275,10 +302,10 @@ mod tests {
     #[test]
     fn reduce_explode_works_0() {
         let input = "[[[[[9,8],1],2],3],4]"[1..].to_string();
-        let mut pair = Pair::from_str(&mut input.chars().rev().collect());
+        let mut pair = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
         
         let input = "[[[[0,9],2],3],4]"[1..].to_string();
-        let expected_pair = Pair::from_str(&mut input.chars().rev().collect());
+        let expected_pair = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
 
         let pair = pair.reduce();
         assert_eq!(expected_pair, pair);
@@ -287,10 +314,10 @@ mod tests {
     #[test]
     fn reduce_explode_works_1() {
         let input = "[7,[6,[5,[4,[3,2]]]]]"[1..].to_string();
-        let mut pair = Pair::from_str(&mut input.chars().rev().collect());
+        let mut pair = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
         
         let input = "[7,[6,[5,[7,0]]]]"[1..].to_string();
-        let expected_pair = Pair::from_str(&mut input.chars().rev().collect());
+        let expected_pair = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
 
         let pair = pair.reduce();
         assert_eq!(expected_pair, pair);
@@ -299,10 +326,10 @@ mod tests {
     #[test]
     fn reduce_explode_works_2() {
         let input = "[[6,[5,[4,[3,2]]]],1]"[1..].to_string();
-        let mut pair = Pair::from_str(&mut input.chars().rev().collect());
+        let mut pair = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
         
         let input = "[[6,[5,[7,0]]],3]"[1..].to_string();
-        let expected_pair = Pair::from_str(&mut input.chars().rev().collect());
+        let expected_pair = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
 
         let pair = pair.reduce();
         assert_eq!(expected_pair, pair);
@@ -311,10 +338,10 @@ mod tests {
     #[test]
     fn reduce_explode_works_3() {
         let input = "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]"[1..].to_string();
-        let mut pair = Pair::from_str(&mut input.chars().rev().collect());
+        let mut pair = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
         
         let input = "[[3,[2,[8,0]]],[9,[5,[7,0]]]]"[1..].to_string();
-        let expected_pair = Pair::from_str(&mut input.chars().rev().collect());
+        let expected_pair = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
 
         let pair = pair.reduce();
         assert_eq!(expected_pair, pair);
@@ -323,10 +350,10 @@ mod tests {
     #[test]
     fn reduce_explode_works_4() {
         let input = "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]"[1..].to_string();
-        let mut pair = Pair::from_str(&mut input.chars().rev().collect());
+        let mut pair = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
         
         let input = "[[3,[2,[8,0]]],[9,[5,[7,0]]]]"[1..].to_string();
-        let expected_pair = Pair::from_str(&mut input.chars().rev().collect());
+        let expected_pair = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
 
         let pair = pair.reduce();
         assert_eq!(expected_pair, pair);
@@ -335,26 +362,23 @@ mod tests {
     #[test]
     fn addition_works() {
         let input = "[1,1]"[1..].to_string();
-        let pair_a = Pair::from_str(&mut input.chars().rev().collect());
+        let pair_a = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
         
         let input = "[2,2]"[1..].to_string();
-        let pair_b = Pair::from_str(&mut input.chars().rev().collect());
+        let pair_b = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
         
         let input = "[[1,1],[2,2]]"[1..].to_string();
-        let expected_pair = Pair::from_str(&mut input.chars().rev().collect());
+        let expected_pair = Pair::from_str(&mut input.chars().rev().collect()).unwrap();
 
         assert_eq!(expected_pair, pair_a + pair_b);
     }
 
     #[test]
-    fn day18_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(4033, part1(&input));
-    }
-
-    #[test]
-    fn day18_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(4864, part2(&input));
+    fn malformed_numbers_are_reported() {
+        assert!(matches!(parse_input("[1,2]\n[[1,2],x]\n"), Err(AocError::Parse { line: 2, column: 8, .. })));
+        assert!(matches!(parse_input("[1,2]]"), Err(AocError::Parse { line: 1, column: 6, .. })));
+        assert!(matches!(parse_input("[1,2"), Err(AocError::Parse { line: 1, column: 4, .. })));
+        assert!(matches!(parse_input("[[[[[1,2],3],4],5],6]"), Err(AocError::Parse { line: 1, column: 5, .. })));
+        assert!(matches!(parse_input(&"[".repeat(1_000_000)), Err(AocError::Parse { line: 1, column: 5, .. })));
     }
 }
@@ -1,83 +1,66 @@
-use aoc_downloader::download_day;
 use std::collections::HashSet;
 use itertools::Itertools;
 
-const DAY: u32 = 19;
-type InputType = Vec<Vec<Coords>>;
-type Coords = (isize, isize, isize);
-
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
-}
+use crate::error::{AocError, Result};
+use crate::utils::{parse, coordinates::{Rotation, Vector}};
+use crate::solution::{Answer, Solution};
 
-fn parse_input(input: &str) -> InputType {
-    input.split("\n\n")
-        .map(|sensorlines| sensorlines.lines()
-            .skip(1)
-            .map(|line| {
-                let coords: Vec<&str> = line.split(",").collect_vec();
-                (coords[0].parse::<isize>().unwrap(),
-                coords[1].parse::<isize>().unwrap(),
-                coords[2].parse::<isize>().unwrap())
-            })
-            .collect_vec()
-        )
-        .collect_vec()
+pub(crate) const DAY: u32 = 19;
+type InputType = Vec<Vec<Coords>>;
+type Coords = Vector<isize, 3>;
+
+fn parse_input(input: &str) -> Result<InputType> {
+    let mut scanners: InputType = Vec::new();
+    parse::lines(input, |line| {
+        if line.starts_with("--- scanner") {
+            scanners.push(Vec::new());
+            return Ok(());
+        }
+        let scanner = scanners.last_mut()
+            .ok_or_else(|| AocError::parse(1, "expected a '--- scanner N ---' header"))?;
+        let coords: Vec<isize> = parse::numbers(line, ',')?;
+        if coords.len() != 3 {
+            return Err(AocError::parse(1, format!("expected 'x,y,z', got '{}'", line)));
+        }
+        scanner.push(Vector::from((coords[0], coords[1], coords[2])));
+        Ok(())
+    })?;
+    if scanners.is_empty() {
+        return Err(AocError::parse(1, "expected at least one scanner"));
+    }
+    Ok(scanners)
 }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    let (beacon_count, distance) = get_beacon_map(&input);
-    println!("Running day {}:\n\tPart 1 {}\n\tPart 2: {}", DAY, beacon_count, distance);
-}
+pub(crate) struct Day19;
 
-fn rotate_z(scan: &Vec<Coords>) -> Vec<Coords> {
-    scan.iter()
-        .map(|coord| (coord.1, -coord.0, coord.2))
-        .collect()
-}
+impl Solution for Day19 {
+    type Input = (u64, u64);
 
-fn rotate_y(scan: &Vec<Coords>) -> Vec<Coords> {
-    scan.iter()
-        .map(|coord| (coord.2, coord.1, -coord.0))
-        .collect()
-}
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        // Both parts fall out of the same scanner alignment, so it is only done once
+        get_beacon_map(&parse_input(input)?)
+    }
 
-fn rotate_coords(scan: &Vec<Coords>) -> Vec<Vec<Coords>> {
-    let mut rotations: Vec<Vec<Coords>> = Vec::new();
-    rotations.push(scan.clone());
-    let mut z_idx = 0;
-    let mut y_idx = 0;
-    loop {
-        for _ in 0..3 {
-            rotations.push(rotate_z(&rotations[z_idx]));
-            z_idx += 1;
-        }
-        z_idx += 1;
-        if z_idx >= 23 {
-            break;
-        }
-        rotations.push(rotate_y(&rotations[y_idx]));
-        y_idx += 1;
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(input.0.into())
     }
 
-    assert_eq!(24, rotations.len());
-    rotations
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(input.1.into())
+    }
 }
 
 fn try_combine_scans(scan_a: &Vec<Coords>, scan_b: &Vec<Coords>) -> Option<(Coords, Vec<Coords>)> {
     let mut signals = scan_a.iter().copied().collect::<HashSet<_>>();
     let distances = scan_a.iter()
         .cartesian_product(scan_b)
-        .map(|(a, b)| (a.0 - b.0, a.1 - b.1, a.2 - b.2));
+        .map(|(&a, &b)| a - b);
 
-    for (delta_x, delta_y, delta_z) in distances {
-        let moved = scan_b.iter().map(|b| (b.0 + delta_x, b.1 + delta_y, b.2 + delta_z));
+    for delta in distances {
+        let moved = scan_b.iter().map(|&b| b + delta);
         if moved.clone().filter(|pos| signals.contains(pos)).count() >= 12 {
             signals.extend(moved);
-            return Some(((delta_x, delta_y, delta_z), signals.into_iter().collect()));
+            return Some((delta, signals.into_iter().collect()));
         }
     }
     None
@@ -85,9 +68,8 @@ fn try_combine_scans(scan_a: &Vec<Coords>, scan_b: &Vec<Coords>) -> Option<(Coor
 
 fn can_combine_scans(input: &InputType, final_scan: &Vec<Coords>) -> Option<(Coords, Vec<Coords>, usize)> {
     for (idx, scan) in input.iter().enumerate() {
-        let rotations = rotate_coords(scan);
-        for rotation in rotations {
-            if let Some((distance, combined)) = try_combine_scans(final_scan, &rotation) {
+        for rotation in Rotation::all() {
+            if let Some((distance, combined)) = try_combine_scans(final_scan, &rotation.apply_all(scan)) {
                 return Some((distance, combined, idx));
             }
         }
@@ -95,7 +77,7 @@ fn can_combine_scans(input: &InputType, final_scan: &Vec<Coords>) -> Option<(Coo
     None
 }
 
-fn get_beacon_map(input: &InputType) -> (u64, u64) {
+fn get_beacon_map(input: &InputType) -> Result<(u64, u64)> {
     let mut input = input.clone();
     let mut distances = Vec::new();
     let mut final_scan = input.remove(0);
@@ -104,29 +86,15 @@ fn get_beacon_map(input: &InputType) -> (u64, u64) {
         input.remove(scan_idx);
         distances.push(distance);
     }
+    if !input.is_empty() {
+        return Err(AocError::unsolvable(format!("{} scanners do not overlap with the others", input.len())));
+    }
 
     let beacon_count = final_scan.len() as u64;
     let distance = distances.iter()
         .tuple_combinations()
-        .map(|(a, b)| (a.0 - b.0).abs() + (a.1 - b.1).abs() + (a.2 - b.2).abs())
+        .map(|(&a, &b)| a.manhattan_distance(b))
         .max()
-        .unwrap() as u64;
-    (beacon_count, distance)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn day19_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(434, get_beacon_map(&input).0);
-    }
-
-    #[test]
-    fn day19_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(11906, get_beacon_map(&input).1);
-    }
+        .unwrap_or(0) as u64;
+    Ok((beacon_count, distance))
 }
@@ -1,28 +1,31 @@
-use aoc_downloader::download_day;
-use regex::Regex;
+use std::str::FromStr;
 
-const DAY: u32 = 2;
+use crate::error::{AocError, Result};
+use crate::utils::{input::InputReader, parse};
+use crate::solution::{Answer, Solution};
 
-enum Operation {
+pub(crate) const DAY: u32 = 2;
+
+#[derive(Debug)]
+pub(crate) enum Operation {
     Forward(i32),
     Down(i32),
     Up(i32),
 }
 
-impl From<&String> for Operation {
-    fn from(input: &String) -> Self {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(r"(.+) (\d)").unwrap();
+impl FromStr for Operation {
+    type Err = AocError;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let (direction, value) = input.split_once(' ')
+            .ok_or_else(|| AocError::parse(1, format!("expected '<direction> <units>', got '{}'", input)))?;
+        let value = parse::number(value, direction.len() + 2)?;
+        match direction {
+            "forward" => Ok(Operation::Forward(value)),
+            "down" => Ok(Operation::Down(value)),
+            "up" => Ok(Operation::Up(value)),
+            _ => Err(AocError::parse(1, format!("unknown direction '{}'", direction))),
         }
-        RE.captures(input).and_then(|captured| {
-            let value = captured[2].parse::<i32>().unwrap();
-            match &captured[1] {
-                "forward" => Some(Operation::Forward(value)),
-                "down" => Some(Operation::Down(value)),
-                "up" => Some(Operation::Up(value)),
-                _ => None,
-            }
-        }).unwrap()
     }
 }
 
@@ -67,25 +70,31 @@ impl Default for Submarine {
     }
 }
 
-fn get_input() -> Vec<String> {
-    use std::io::BufRead;
-    download_day((DAY) as u32, "input").unwrap();
-
-    let file = std::fs::File::open(format!("input/input{}.txt", DAY)).unwrap();
-    let reader = std::io::BufReader::new(&file);
-    reader.lines().collect::<Result<_, _>>().unwrap()
+fn parse_input(input: &str) -> Result<Vec<Operation>> {
+    parse::lines(input, Operation::from_str)
 }
 
-fn parse_input(input: Vec<String>) -> Vec<Operation> {
-    input.iter()
-        .map(|inp| Operation::from(inp))
-        .collect::<Vec<_>>()
-}
+pub(crate) struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Operation>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(input);
-    println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
+    fn stream(&self, input: InputReader) -> Option<Result<(Answer, Answer)>> {
+        let operations = parse::stream(input.lines(), Operation::from_str);
+        Some(dive_both(operations).map(|(part1, part2)| (part1.into(), part2.into())))
+    }
 }
 
 fn part1(input: &Vec<Operation>) -> i32{
@@ -104,16 +113,22 @@ fn part2(input: &Vec<Operation>) -> i32 {
     uboot.get_traveled_distance()
 }
 
+// Both submarines follow the same commands, one operation at a time
+fn dive_both(operations: impl Iterator<Item = Result<Operation>>) -> Result<(i32, i32)> {
+    let mut uboot = Submarine::default();
+    let mut uboot2 = Submarine::default();
+    for operation in operations {
+        let operation = operation?;
+        uboot.dive(&operation);
+        uboot2.dive2(&operation);
+    }
+    Ok((uboot.get_traveled_distance(), uboot2.get_traveled_distance()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn day2_part1_output() {
-        let input = parse_input(get_input());
-        assert_eq!(1694130, part1(&input));
-    }
-
     #[test]
     fn day2_part1_testcase1() {
         let input = vec![
@@ -124,16 +139,10 @@ mod tests {
             String::from("down 8"),
             String::from("forward 2"),
         ];
-        let input = parse_input(input);
+        let input = parse_input(&input.join("\n")).unwrap();
         assert_eq!(150, part1(&input));
     }
 
-    #[test]
-    fn day2_part2_output() {
-        let input = parse_input(get_input());
-        assert_eq!(1698850445, part2(&input));
-    }
-
     #[test]
     fn day2_part2_testcase1() {
         let input = vec![
@@ -144,7 +153,21 @@ mod tests {
             String::from("down 8"),
             String::from("forward 2"),
         ];
-        let input = parse_input(input);
+        let input = parse_input(&input.join("\n")).unwrap();
         assert_eq!(900, part2(&input));
     }
+
+    #[test]
+    fn day2_streams_both_parts() {
+        let reader = InputReader::from_string("forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n".to_string());
+        assert_eq!((Answer::Number(150), Answer::Number(900)), Day2.stream(reader).unwrap().unwrap());
+    }
+
+    #[test]
+    fn day2_reports_malformed_lines() {
+        let error = parse_input("forward 5\nsideways 3\n").unwrap_err();
+        assert!(matches!(error, AocError::Parse { line: 2, column: 1, .. }), "{}", error);
+        let error = parse_input("forward 5\ndown x\n").unwrap_err();
+        assert!(matches!(error, AocError::Parse { line: 2, column: 6, .. }), "{}", error);
+    }
 }
@@ -1,120 +1,122 @@
-use aoc_downloader::download_day;
-use std::collections::HashSet;
+use std::iter;
 
-const DAY: u32 = 20;
+use crate::error::{AocError, Result};
+use crate::utils::{coordinates::Vector, grid::Grid, image::{NIGHT, WHITE}, render::{Renderer, ASCII}, sparse::{Point, SparseGrid}};
+use crate::solution::{Answer, Frames, Images, Solution};
+
+pub(crate) const DAY: u32 = 20;
 type InputType = Input;
-type Coords = (isize, isize);
+type Image = SparseGrid<bool>;
 
-struct Input {
+pub(crate) struct Input {
     image: Image,
     algorithm: Vec<bool>,
 }
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
+fn pixel(c: char) -> Option<bool> {
+    match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    }
 }
 
-fn parse_input(input: &str) -> InputType {
-    let input: Vec<String> = input.lines()
-        .map(|line| line.to_string())
-        .collect();
-
-    let algorithm: Vec<bool> = input[0].chars()
-        .map(|c| if c == '#' { true } else { false})
-        .collect();
-
-    let original_image: Vec<Vec<char>> = input.iter()
-        .skip(2)
-        .map(|line| line.chars()
-            .collect())
-        .collect();
-
-    let mut image: HashSet<Coords> = HashSet::new();
-    for (y, row) in original_image.iter().enumerate() {
-        for (x, pixel) in row.iter().enumerate() {
-            if *pixel == '#' {
-                image.insert((y as isize, x as isize));
-            }
-        }
+fn parse_input(input: &str) -> Result<InputType> {
+    let (algorithm, original_image) = input.split_once("\n\n")
+        .ok_or_else(|| AocError::parse(1, "expected the algorithm, an empty line and the image"))?;
+
+    let algorithm = algorithm.chars()
+        .enumerate()
+        .map(|(idx, c)| pixel(c).ok_or_else(|| AocError::parse(idx + 1, format!("expected '#' or '.', got '{}'", c))))
+        .collect::<Result<Vec<bool>>>()?;
+    if algorithm.len() != 512 {
+        return Err(AocError::parse(1, format!("expected 512 entries in the algorithm, got {}", algorithm.len())));
     }
-    let image = Image::new(image, false);
 
-    Input {
+    let original_image = Grid::parse(original_image, "'#' or '.'", pixel)
+        .map_err(|error| error.at_line(3))?;
+
+    let image = Image::from_grid(&original_image, false);
+
+    Ok(Input {
         image,
         algorithm,
-    }
+    })
 }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart 1 {}\n\tPart 2: {}", DAY, part1(&input), part2(&input));
-}
+pub(crate) struct Day20;
 
-#[derive(Clone)]
-struct Image {
-    pixel: HashSet<(isize, isize)>,
-    size_x: (isize, isize),
-    size_y: (isize, isize),
-    empty_space: bool,
-}
+impl Solution for Day20 {
+    type Input = InputType;
 
-impl Image {
-    pub fn new(image: HashSet<Coords>, empty_space: bool) -> Self {
-        let min_y = image.iter().map(|pixel| pixel.0).min().unwrap();
-        let max_y = image.iter().map(|pixel| pixel.0).max().unwrap();
-        let min_x = image.iter().map(|pixel| pixel.1).min().unwrap();
-        let max_x = image.iter().map(|pixel| pixel.1).max().unwrap();
-
-        Image {
-            pixel: image,
-            size_x: (min_x, max_x),
-            size_y: (min_y, max_y),
-            empty_space,
-        }
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
     }
 
-    pub fn get_pixel(&self, current: Coords) -> usize {
-        if current.0 < self.size_y.0 || current.0 > self.size_y.1 || current.1 < self.size_x.0 || current.1 > self.size_x.1 {
-            self.empty_space as usize
-        } else {
-            self.pixel.contains(&current) as usize
-        }
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
     }
-}
 
-fn get_pointer(image: &Image, current: Coords) -> usize {
-    lazy_static! {
-        static ref OFFSETS: Vec<(isize, isize)> = vec![
-            (-1, -1), (-1, 0), (-1, 1),
-            (0, -1),  (0, 0),  (0, 1), 
-            (1, -1),  (1, 0),  (1, 1),
-        ];
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part2(input).into())
     }
-    let mut pointer = 0;
-    for (idx, offset) in OFFSETS.iter().enumerate() {
-        pointer |= image.get_pixel((current.0 + offset.0, current.1 + offset.1)) << (8 - idx)
+
+    // The image after every round of enhancement, up to the 50 part 2 asks for
+    fn frames(&self, input: Self::Input) -> Option<Frames> {
+        let Input { image, algorithm } = input;
+        let render = |round: usize, image: &Image| format!(
+            "Round {}, {} lit, the rest is {}\n{}",
+            round,
+            image.values().filter(|&&lit| lit).count(),
+            if *image.background() { "lit" } else { "dark" },
+            Renderer::bools(ASCII).sparse(image),
+        );
+        let first = render(0, &image);
+        Some(Box::new(iter::once(first).chain((1..=50).scan(image, move |image, round| {
+            *image = enhance_once(image, &algorithm);
+            Some(render(round, image))
+        }))))
+    }
+
+    fn images(&self, input: Self::Input) -> Option<Images> {
+        let Input { image, algorithm } = input;
+        let paint = |image: &Image| image.to_grid().map(|&lit| if lit { WHITE } else { NIGHT });
+        let first = paint(&image);
+        Some(Box::new(iter::once(first).chain((1..=50).scan(image, move |image, _| {
+            *image = enhance_once(image, &algorithm);
+            Some(paint(image))
+        }))))
     }
-    pointer as usize
 }
 
-fn enhance(mut image: Image, algorithm: &Vec<bool>, rounds: usize) -> Image {
-    for _ in 0..rounds {
-        let mut new_image: HashSet<Coords> = HashSet::new();
+fn get_pointer(image: &Image, current: Point) -> usize {
+    // Read row by row, the top left pixel is the most significant bit
+    const OFFSETS: [Point; 9] = [
+        Vector::new([-1, -1]), Vector::new([0, -1]), Vector::new([1, -1]),
+        Vector::new([-1, 0]),  Vector::new([0, 0]),  Vector::new([1, 0]),
+        Vector::new([-1, 1]),  Vector::new([0, 1]),  Vector::new([1, 1]),
+    ];
+    let mut pointer = 0;
+    for (idx, &offset) in OFFSETS.iter().enumerate() {
+        pointer |= (image[current + offset] as usize) << (8 - idx)
+    }
+    pointer
+}
 
-        for y in (image.size_y.0-1)..=(image.size_y.1+1) {
-            for x in (image.size_x.0-1)..=(image.size_x.1+1) {
-                let pointer = get_pointer(&image, (y, x));
+fn enhance_once(image: &Image, algorithm: &[bool]) -> Image {
+    // The infinite rest of the image is all dark or all lit, so it turns as a whole
+    let new_background = if *image.background() { algorithm[511] } else { algorithm[0] };
+    let mut new_image = Image::new(new_background);
 
-                if algorithm[pointer] {
-                    new_image.insert((y, x));
-                }
-            }
-        }
+    for pixel in image.window(1) {
+        new_image.insert(pixel, algorithm[get_pointer(image, pixel)]);
+    }
+    new_image
+}
 
-        let new_empty = if image.empty_space { algorithm[511] } else { algorithm[0] };
-        image = Image::new(new_image, new_empty);
+fn enhance(mut image: Image, algorithm: &[bool], rounds: usize) -> Image {
+    for _ in 0..rounds {
+        image = enhance_once(&image, algorithm);
     }
     image
 }
@@ -123,30 +125,11 @@ fn enhance(mut image: Image, algorithm: &Vec<bool>, rounds: usize) -> Image {
 fn part1(input: &InputType) -> usize {
     let image = enhance(input.image.clone(), &input.algorithm, 2);
 
-    image.pixel.len()
+    image.values().filter(|&&lit| lit).count()
 }
 
 fn part2(input: &InputType) -> usize {
     let image = enhance(input.image.clone(), &input.algorithm, 50);
 
-    image.pixel.len()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    //#[ignore]
-    #[test]
-    fn day20_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(5231, part1(&input));
-    }
-
-    //#[ignore]
-    #[test]
-    fn day20_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(14279, part2(&input));
-    }
+    image.values().filter(|&&lit| lit).count()
 }
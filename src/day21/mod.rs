@@ -1,31 +1,48 @@
-use aoc_downloader::download_day;
 use cached::proc_macro::cached;
 use regex::Regex;
 
-const DAY: u32 = 21;
-type InputType = Vec<u64>;
+use crate::error::{AocError, Result};
+use crate::utils::parse;
+use crate::solution::{Answer, Solution};
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
-}
+pub(crate) const DAY: u32 = 21;
+type InputType = Vec<u64>;
 
-fn parse_input(input: &str) -> InputType {
+fn parse_input(input: &str) -> Result<InputType> {
     lazy_static! {
-        static ref RE: Regex = Regex::new(r"Player \d starting position: (\d+)").unwrap();
+        static ref RE: Regex = Regex::new(r"^Player \d starting position: (\d+)$").unwrap();
     }
 
-    input.lines()
-        .filter(|line| *line != "")
-        .map(|line| RE.captures(line).and_then(|captured|
-                Some(captured[1].parse::<u64>().unwrap())).unwrap())
-        .collect()
+    let positions = parse::lines(input, |line| {
+        let captured = parse::captures(&RE, line, "'Player <n> starting position: <position>'")?;
+        let position = parse::capture(&captured, 1)?;
+        if !(1..=10).contains(&position) {
+            return Err(AocError::parse(parse::capture_column(&captured, 1), "positions go from 1 to 10"));
+        }
+        Ok(position)
+    })?;
+    if positions.len() != 2 {
+        return Err(AocError::parse(1, format!("expected two players, got {}", positions.len())));
+    }
+    Ok(positions)
 }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart 1 {}\n\tPart 2: {}", DAY, part1(&input), part2(&input));
+pub(crate) struct Day21;
+
+impl Solution for Day21 {
+    type Input = InputType;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
 }
 
 #[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
@@ -140,20 +157,3 @@ fn part2(input: &InputType) -> u64 {
     let (player_a_wins, player_b_wins) = play_round(input[0]-1, 0, input[1]-1, 0);
     std::cmp::max(player_a_wins, player_b_wins)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn day21_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(503478, part1(&input));
-    }
-
-    #[test]
-    fn day21_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(716241959649754, part2(&input));
-    }
-}
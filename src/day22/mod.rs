@@ -1,151 +1,99 @@
-use aoc_downloader::download_day;
 use regex::Regex;
 
-const DAY: u32 = 22;
+use crate::error::{AocError, Result};
+use crate::utils::{parse, coordinates::Vector};
+use crate::solution::{Answer, Solution};
+
+pub(crate) const DAY: u32 = 22;
 type InputType = Vec<Cube>;
+type Coords = Vector<isize, 3>;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-struct Cube {
-    x_start: isize,
-    x_end: isize,
-    y_start: isize,
-    y_end: isize,
-    z_start: isize,
-    z_end: isize,
+pub(crate) struct Cube {
+    // Inclusive min, exclusive max
+    min: Coords,
+    max: Coords,
     state: bool,
 }
 
 impl Cube { 
     fn intersect(&self, other: &Self) -> bool {
-        other.x_end > self.x_start && other.x_start < self.x_end &&
-        other.y_end > self.y_start && other.y_start < self.y_end &&
-        other.z_end > self.z_start && other.z_start < self.z_end
+        (0..3).all(|axis| other.max[axis] > self.min[axis] && other.min[axis] < self.max[axis])
     }
 
+    // Cuts off the parts of self that stick out of other, leaving self as the overlap
     fn split_cube(&mut self, other: &Self) -> Vec<Self> {
         let mut new_cubes = Vec::new();
 
-        if self.x_start < other.x_start {
-            new_cubes.push(Cube {
-                x_start: self.x_start,
-                x_end: other.x_start,
-                y_start: self.y_start,
-                y_end: self.y_end,
-                z_start: self.z_start,
-                z_end: self.z_end,
-                state: self.state,
-            });
-            self.x_start = other.x_start;
-        }
-        if self.x_end > other.x_end {
-            new_cubes.push(Cube {
-                x_start: other.x_end,
-                x_end: self.x_end,
-                y_start: self.y_start,
-                y_end: self.y_end,
-                z_start: self.z_start,
-                z_end: self.z_end,
-                state: self.state,
-            });
-            self.x_end = other.x_end;
-        }
-        if self.y_start < other.y_start {
-            new_cubes.push(Cube {
-                x_start: self.x_start,
-                x_end: self.x_end,
-                y_start: self.y_start,
-                y_end: other.y_start,
-                z_start: self.z_start,
-                z_end: self.z_end,
-                state: self.state,
-            });
-            self.y_start= other.y_start;
-        }
-        if self.y_end > other.y_end{
-            new_cubes.push(Cube {
-                x_start: self.x_start,
-                x_end: self.x_end,
-                y_start: other.y_end,
-                y_end: self.y_end,
-                z_start: self.z_start,
-                z_end: self.z_end,
-                state: self.state,
-            });
-            self.y_end = other.y_end;
-        }
-        if self.z_start < other.z_start {
-            new_cubes.push(Cube {
-                x_start: self.x_start,
-                x_end: self.x_end,
-                y_start: self.y_start,
-                y_end: self.y_end,
-                z_start: self.z_start,
-                z_end: other.z_start,
-                state: self.state,
-            });
-            self.z_start= other.z_start;
-        }
-        if self.z_end > other.z_end{
-            new_cubes.push(Cube {
-                x_start: self.x_start,
-                x_end: self.x_end,
-                y_start: self.y_start,
-                y_end: self.y_end,
-                z_start: other.z_end,
-                z_end: self.z_end,
-                state: self.state,
-            });
-            self.z_end = other.z_end;
+        for axis in 0..3 {
+            if self.min[axis] < other.min[axis] {
+                let mut below = *self;
+                below.max[axis] = other.min[axis];
+                new_cubes.push(below);
+                self.min[axis] = other.min[axis];
+            }
+            if self.max[axis] > other.max[axis] {
+                let mut above = *self;
+                above.min[axis] = other.max[axis];
+                new_cubes.push(above);
+                self.max[axis] = other.max[axis];
+            }
         }
 
         new_cubes
     }
 
     fn get_size(&self) -> isize {
-        (self.x_end - self.x_start) * (self.y_end - self.y_start) * (self.z_end - self.z_start)
+        (self.max - self.min).coordinates().iter().product()
     }
 }
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
-}
-
-fn parse_input(input: &str) -> InputType {
+fn parse_input(input: &str) -> Result<InputType> {
     lazy_static! {
-        static ref RE: Regex = Regex::new(r"(on|off) x=(-?\d+)..(-?\d+),y=(-?\d+)..(-?\d+),z=(-?\d+)..(-?\d+)").unwrap();
+        static ref RE: Regex = Regex::new(r"^(on|off) x=(-?\d+)\.\.(-?\d+),y=(-?\d+)\.\.(-?\d+),z=(-?\d+)\.\.(-?\d+)$").unwrap();
     }
 
-    input.lines()
-        .filter(|line| *line != "")
-        .map(|line| RE.captures(line).and_then(|captured| {
-                let state = if captured[1] == *"on" { true } else { false };
-                let x = (captured[2].parse::<isize>().unwrap(), captured[3].parse::<isize>().unwrap() + 1);
-                let y = (captured[4].parse::<isize>().unwrap(), captured[5].parse::<isize>().unwrap() + 1);
-                let z = (captured[6].parse::<isize>().unwrap(), captured[7].parse::<isize>().unwrap() + 1);
-                Some(Cube {
-                    x_start: x.0,
-                    x_end: x.1,
-                    y_start: y.0,
-                    y_end: y.1,
-                    z_start: z.0,
-                    z_end: z.1,
-                    state
-                })
-        }).unwrap())
-        .collect()
+    parse::lines(input, |line| {
+        let captured = parse::captures(&RE, line, "'on|off x=<from>..<to>,y=<from>..<to>,z=<from>..<to>'")?;
+        let state = if captured[1] == *"on" { true } else { false };
+        let range = |idx: usize| -> Result<(isize, isize)> {
+            let (start, end): (isize, isize) = (parse::capture(&captured, idx)?, parse::capture(&captured, idx + 1)?);
+            if start > end {
+                return Err(AocError::parse(parse::capture_column(&captured, idx), "ranges have to go from low to high"));
+            }
+            Ok((start, end + 1))
+        };
+        let (x, y, z) = (range(2)?, range(4)?, range(6)?);
+        Ok(Cube {
+            min: Vector::from((x.0, y.0, z.0)),
+            max: Vector::from((x.1, y.1, z.1)),
+            state
+        })
+    })
 }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart 1: {}\n\tPart 2: {}", DAY, part1(&input), part2(&input));
+pub(crate) struct Day22;
+
+impl Solution for Day22 {
+    type Input = InputType;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
 }
 
 fn part1(input: &InputType) -> isize {
     let mut cubes: Vec<Cube> = Vec::new();
 
-    for in_cube in input[..20].iter() {
+    for in_cube in input.iter().take(20) {
         let mut new_cubes: Vec<Cube> = Vec::new();
         for cube in &mut cubes {
             if !cube.intersect(in_cube) {
@@ -195,16 +143,38 @@ fn part2(input: &InputType) -> isize {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    #[test]
-    fn day22_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(602574, part1(&input));
+    use std::collections::HashSet;
+
+    use crate::utils::gen;
+
+    // Switches every cube on its own
+    fn count_voxels(input: &InputType) -> isize {
+        let mut on = HashSet::new();
+        for cuboid in input {
+            for x in cuboid.min[0]..cuboid.max[0] {
+                for y in cuboid.min[1]..cuboid.max[1] {
+                    for z in cuboid.min[2]..cuboid.max[2] {
+                        if cuboid.state {
+                            on.insert((x, y, z));
+                        } else {
+                            on.remove(&(x, y, z));
+                        }
+                    }
+                }
+            }
+        }
+        on.len() as isize
     }
 
     #[test]
-    fn day22_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(1288707160324706, part2(&input));
+    fn day22_split_cubes_match_voxels() {
+        gen::check(200, |rng| {
+            let input: InputType = (0..rng.range(1..=8)).map(|_| {
+                let min = Vector::from((rng.range(-6..=6) as isize, rng.range(-6..=6) as isize, rng.range(-6..=6) as isize));
+                let extent = Vector::from((rng.range(1..=6) as isize, rng.range(1..=6) as isize, rng.range(1..=6) as isize));
+                Cube { min, max: min + extent, state: rng.chance(60) }
+            }).collect();
+            assert_eq!(count_voxels(&input), part2(&input), "{:?}", input);
+        });
     }
 }
@@ -1,29 +1,36 @@
-use aoc_downloader::download_day;
-use regex::Regex;
+use crate::error::{AocError, Result};
+use crate::utils::parse;
+use crate::solution::{Answer, Solution};
 
-const DAY: u32 = 23;
+pub(crate) const DAY: u32 = 23;
 type InputType = Vec<String>;
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
+// Walls, open space and amphipods, the answers themselves were worked out by hand
+fn parse_input(input: &str) -> Result<InputType> {
+    parse::lines(input, |line| {
+        match line.char_indices().find(|&(_, c)| !"#. ABCD".contains(c)) {
+            Some((idx, c)) => Err(AocError::parse(idx + 1, format!("expected '#', '.', ' ' or an amphipod A to D, got '{}'", c))),
+            None => Ok(line.to_string()),
+        }
+    })
 }
 
-fn parse_input(input: &str) -> InputType {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"(on|off) x=(-?\d+)..(-?\d+),y=(-?\d+)..(-?\d+),z=(-?\d+)..(-?\d+)").unwrap();
+pub(crate) struct Day23;
+
+impl Solution for Day23 {
+    type Input = InputType;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
     }
 
-    input.lines()
-        .filter(|line| *line != "")
-        .map(|line| line.to_string())
-        .collect()
-}
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart 1: {}\n\tPart 2: {}", DAY, part1(&input), part2(&input));
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
 }
 
 fn part1(_input: &InputType) -> isize {
@@ -41,14 +48,8 @@ mod tests {
     use super::*;
 
     #[test]
-    fn day23_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(15358, part1(&input));
-    }
-
-    #[test]
-    fn day23_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(52156, part2(&input));
+    fn day23_reports_unknown_cells() {
+        let error = parse_input("#############\n#...........#\n###B#C#E#D###\n").unwrap_err();
+        assert!(matches!(error, AocError::Parse { line: 3, column: 8, .. }), "{}", error);
     }
 }
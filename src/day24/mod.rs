@@ -1,19 +1,28 @@
 use std::{ops::{IndexMut, Index}, str::FromStr};
-use aoc_downloader::download_day;
 use cached::proc_macro::cached;
 use regex::Regex;
 
-const DAY: u32 = 24;
+use crate::error::{AocError, Result};
+use crate::utils::parse;
+use crate::solution::{Answer, Solution};
+
+pub(crate) const DAY: u32 = 24;
 type InputType = Vec<u64>;
 
+#[derive(Debug, Clone, Copy)]
+enum Operant {
+    Register(char),
+    Number(i64),
+}
+
 #[derive(Debug, Clone)]
 enum Command {
     Input((char, i64)),
-    Add((char, String)),
-    Mul((char, String)),
-    Div((char, String)),
-    Mod((char, String)),
-    Eql((char, String))
+    Add((char, Operant)),
+    Mul((char, Operant)),
+    Div((char, Operant)),
+    Mod((char, Operant)),
+    Eql((char, Operant))
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -41,21 +50,18 @@ impl Alu {
 
         match command {
             Input((register, operant)) => self[register] = *operant,
-            Add((register, operant)) => self[register] += self.get_value(&operant),
-            Mul((register, operant)) => self[register] *= self.get_value(&operant),
-            Div((register, operant)) => self[register] /= self.get_value(&operant),
-            Mod((register, operant)) => self[register] %= self.get_value(&operant),
-            Eql((register, operant)) => self[register] = if self[register] == self.get_value(&operant) { 1 } else { 0 },
+            Add((register, operant)) => self[register] += self.get_value(operant),
+            Mul((register, operant)) => self[register] *= self.get_value(operant),
+            Div((register, operant)) => self[register] /= self.get_value(operant),
+            Mod((register, operant)) => self[register] %= self.get_value(operant),
+            Eql((register, operant)) => self[register] = if self[register] == self.get_value(operant) { 1 } else { 0 },
         }
     }
 
-    fn get_value(&self, operant: &str) -> i64 {
+    fn get_value(&self, operant: &Operant) -> i64 {
         match operant {
-            "w" => self.w,
-            "x" => self.x,
-            "y" => self.y,
-            "z" => self.z,
-            number => number.parse::<i64>().unwrap(),
+            Operant::Register(register) => self[register],
+            Operant::Number(number) => *number,
         }
     }
 }
@@ -87,53 +93,69 @@ impl IndexMut<&char> for Alu {
 }
 
 impl FromStr for Command {
-    type Err = std::io::Error;
+    type Err = AocError;
 
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
+    fn from_str(input: &str) -> Result<Self> {
         lazy_static! {
-            static ref RE: Regex = Regex::new(r"([a-zA-Z]{3}) ([a-zA-Z])( (.+))?").unwrap();
+            static ref RE: Regex = Regex::new(r"^([a-z]{3}) ([wxyz])( (-?\d+|[wxyz]))?$").unwrap();
+        }
+        let captured = parse::captures(&RE, input, "'<instruction> <register> [<register>|<number>]'")?;
+        let register = captured[2].chars().nth(0).unwrap();
+        if &captured[1] == "inp" {
+            return match captured.get(4) {
+                None => Ok(Command::Input((register, 0))),
+                Some(operant) => Err(AocError::parse(operant.start() + 1, "inp only takes a register")),
+            };
+        }
+        let operant = captured.get(4)
+            .ok_or_else(|| AocError::parse(input.len() + 1, format!("{} needs a second operant", &captured[1])))?;
+        let operant = match operant.as_str() {
+            "w" | "x" | "y" | "z" => Operant::Register(operant.as_str().chars().nth(0).unwrap()),
+            number => Operant::Number(parse::number(number, operant.start() + 1)?),
+        };
+        match &captured[1] {
+            "add" => Ok(Command::Add((register, operant))),
+            "mul" => Ok(Command::Mul((register, operant))),
+            "div" => Ok(Command::Div((register, operant))),
+            "mod" => Ok(Command::Mod((register, operant))),
+            "eql" => Ok(Command::Eql((register, operant))),
+            instruction => Err(AocError::parse(1, format!("unknown instruction '{}'", instruction))),
         }
-        Ok(RE.captures(input).and_then(|captured| {
-            match &captured[1] {
-                "inp" => Some(Command::Input((captured[2].chars().nth(0).unwrap(), 0))),
-                "add" => Some(Command::Add((captured[2].chars().nth(0).unwrap(), captured[4].to_string()))),
-                "mul" => Some(Command::Mul((captured[2].chars().nth(0).unwrap(), captured[4].to_string()))),
-                "div" => Some(Command::Div((captured[2].chars().nth(0).unwrap(), captured[4].to_string()))),
-                "mod" => Some(Command::Mod((captured[2].chars().nth(0).unwrap(), captured[4].to_string()))),
-                "eql" => Some(Command::Eql((captured[2].chars().nth(0).unwrap(), captured[4].to_string()))),
-                _ => unreachable!("Unexpedted Command encountered while parsing"),
-            }
-        }).unwrap())
     }
 }
 
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
+fn parse_program(input: &str) -> Result<Vec<Command>> {
+    parse::lines(input, Command::from_str)
 }
 
-fn parse_input(input: &str) -> InputType {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"(on|off) x=(-?\d+)..(-?\d+),y=(-?\d+)..(-?\d+),z=(-?\d+)..(-?\d+)").unwrap();
-    }
-
-    let _: Vec<Command> = input.lines()
-        .filter(|line| *line != "")
-        .map(|line| Command::from_str(line).unwrap())
-        .collect();
+fn parse_input(input: &str) -> Result<InputType> {
+    parse_program(input)?;
     if let Some(numbers) = get_valid_values(0, 0) {
         return numbers.iter()
-            .map(|number| number.parse::<u64>().unwrap())
-            .collect::<Vec<_>>()
+            .map(|number| number.parse::<u64>()
+                .map_err(|_| AocError::unsolvable(format!("{} is not a model number", number))))
+            .collect();
     }
-    vec![0]
+    Ok(vec![0])
 }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart 1: {}\n\tPart 2: {}", DAY, part1(&input), part2(&input));
+pub(crate) struct Day24;
+
+impl Solution for Day24 {
+    type Input = InputType;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        part2(input).map(Answer::from)
+    }
 }
 
 // This could also be read in by finding the indexes and then stepping through the program +18
@@ -183,115 +205,57 @@ fn get_valid_values(number_len: usize, z: i64) -> Option<Vec<String>> {
     Some(numbers)
 }
 
-fn part1(input: &InputType) -> u64{
-    *input.iter().max().unwrap()
+fn part1(input: &InputType) -> Result<u64> {
+    input.iter().max().copied().ok_or_else(|| AocError::unsolvable("no model number is accepted"))
 }
 
-fn part2(input: &InputType) -> u64 {
-    *input.iter().min().unwrap()
+fn part2(input: &InputType) -> Result<u64> {
+    input.iter().min().copied().ok_or_else(|| AocError::unsolvable("no model number is accepted"))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    /*
-    #[test]
-    fn alu_example1() {
-        let input = "
-inp x
-mul x -1";
-        let mut commands = parse_input(input);
+    // Runs program with inputs fed to its inp instructions in order
+    fn run(program: &str, inputs: &[i64]) -> Alu {
+        let mut inputs = inputs.iter();
         let mut alu = Alu::default();
-
-        for (i, command) in commands.iter().enumerate() {
-            if let Command::Input((register, _)) = command {
-                commands[i] = Command::Input((*register, 100));
-                break;
+        for command in parse_program(program).unwrap() {
+            match command {
+                Command::Input((register, _)) => alu.execute(&Command::Input((register, *inputs.next().unwrap()))),
+                command => alu.execute(&command),
             }
         }
-
-        for command in &commands {
-            alu.execute(command);
-        }
-        assert_eq!(-100, alu.x);
+        alu
     }
 
     #[test]
-    fn alu_example2() {
-        let input = "
-inp z
-inp x
-mul z 3
-eql z x";
-        let inputs = vec![100, 300];
-        let commands = parse_input(input);
-        let mut input_counter = 0;
-        let mut new_commands = commands.clone();
-        for (i, command) in commands.iter().enumerate() {
-            if let Command::Input((register, _)) = command {
-                new_commands[i] = Command::Input((*register, inputs[input_counter]));
-                input_counter += 1;
-                if input_counter == 2 {
-                    break;
-                }
-            }
-        }
-
-        let mut alu = Alu::default();
-        for command in &new_commands {
-            alu.execute(command);
-        }
-        assert_eq!(1, alu.z);
-
-        let input = vec![
-            Command::Input(('z', 200)),
-            Command::Input(('x', 300)),
-            Command::Mul(('z', "-1".to_string())),
-            Command::Eql(('z', "x".to_string())),
-        ];
-        let mut alu = Alu::default();
-        for command in &input {
-            alu.execute(command);
-        }
-        assert_eq!(0, alu.z);
+    fn alu_example1() {
+        assert_eq!(-100, run("inp x\nmul x -1\n", &[100]).x);
     }
 
     #[test]
-    fn alu_example3() {
-        let input = vec![
-            Command::Input(('w', 10)),
-            Command::Add(('z', "2".to_string())),
-            Command::Mod(('z', "2".to_string())),
-            Command::Div(('w', "2".to_string())),
-            Command::Add(('y', "w".to_string())),
-            Command::Mod(('y', "2".to_string())),
-            Command::Div(('w', "2".to_string())),
-            Command::Add(('x', "w".to_string())),
-            Command::Mod(('x', "2".to_string())),
-            Command::Div(('w', "2".to_string())),
-            Command::Mod(('w', "2".to_string())),
-        ];
-        let mut alu = Alu::default();
-        for command in &input {
-            alu.execute(command);
-        }
-        assert_eq!(0, alu.z);
-        assert_eq!(1, alu.y);
-        assert_eq!(0, alu.x);
-        assert_eq!(1, alu.w);
+    fn alu_example2() {
+        let program = "inp z\ninp x\nmul z 3\neql z x\n";
+        assert_eq!(1, run(program, &[100, 300]).z);
+        assert_eq!(0, run(program, &[200, 300]).z);
     }
-    */
 
     #[test]
-    fn day24_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(51939397989999, part1(&input));
+    fn alu_example3() {
+        let program = "inp w\nadd z w\nmod z 2\ndiv w 2\nadd y w\nmod y 2\ndiv w 2\nadd x w\nmod x 2\ndiv w 2\nmod w 2\n";
+        let alu = run(program, &[10]);
+        assert_eq!((1, 0, 1, 0), (alu.w, alu.x, alu.y, alu.z));
     }
 
     #[test]
-    fn day24_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(11717131211195, part2(&input));
+    fn day24_reports_malformed_instructions() {
+        let error = parse_program("inp w\nadd x 99999999999999999999\n").unwrap_err();
+        assert!(matches!(error, AocError::Parse { line: 2, column: 7, .. }), "{}", error);
+        let error = parse_program("inp w\nmul x\n").unwrap_err();
+        assert!(matches!(error, AocError::Parse { line: 2, column: 6, .. }), "{}", error);
+        let error = parse_program("sub x 1\n").unwrap_err();
+        assert!(matches!(error, AocError::Parse { line: 1, column: 1, .. }), "{}", error);
     }
 }
@@ -1,84 +1,118 @@
-use aoc_downloader::download_day;
+use std::iter;
 
-const DAY: u32 = 25;
-type InputType = Vec<Vec<char>>;
+use crate::error::{AocError, Result};
+use crate::utils::{grid::{Grid, Offset}, image::{CYAN, NIGHT, ORANGE}, render::Renderer};
+use crate::solution::{Answer, Frames, Images, Solution};
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
-}
+pub(crate) const DAY: u32 = 25;
+type InputType = Grid<char>;
 
-fn parse_input(input: &str) -> InputType {
-    input.lines()
-        .filter(|line| *line != "")
-        .map(|line| line.chars().collect::<Vec<char>>())
-        .collect()
+fn parse_input(input: &str) -> Result<InputType> {
+    Grid::parse(input, "'>', 'v' or '.'", |c| Some(c).filter(|c| ">v.".contains(*c)))
 }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart 1: {}\n\tPart 2: {}", DAY, part1(&input), part2(&input));
+pub(crate) struct Day25;
+
+impl Solution for Day25 {
+    type Input = InputType;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
+
+    // Both herds move once per frame, until neither of them can
+    fn frames(&self, mut input: Self::Input) -> Option<Frames> {
+        let render = |herds: &InputType| Renderer::new(|&spot: &char| spot).grid(herds);
+        let first = format!("Step 0\n{}", render(&input));
+        let mut step = 0;
+        let mut moved = true;
+        Some(Box::new(iter::once(first).chain(iter::from_fn(move || {
+            if !moved {
+                return None;
+            }
+            step += 1;
+            moved = move_herds(&mut input);
+            Some(format!("Step {}{}\n{}", step, if moved { "" } else { ", nothing moved" }, render(&input)))
+        }))))
+    }
+
+    fn images(&self, mut input: Self::Input) -> Option<Images> {
+        let paint = |herds: &InputType| herds.map(|&spot| match spot {
+            '>' => ORANGE,
+            'v' => CYAN,
+            _ => NIGHT,
+        });
+        let first = paint(&input);
+        let mut moved = true;
+        Some(Box::new(iter::once(first).chain(iter::from_fn(move || {
+            if !moved {
+                return None;
+            }
+            moved = move_herds(&mut input);
+            Some(paint(&input))
+        }))))
+    }
 }
 
-fn part1(input: &InputType) -> u64{
-    let mut input = input.clone();
+const EAST: Offset = (0, 1);
+const SOUTH: Offset = (1, 0);
 
-    let max_x = input[0].len();
-    let max_y = input.len();
+// Far more than any puzzle input needs, some herds never come to a stop
+const MAX_ROUNDS: u64 = 100_000;
 
-    let mut rounds = 0;
-    let mut moved = true;
-    while moved {
-        moved = false;
-        let mut next_step = input.clone();
-        for (y, row) in input.iter().enumerate() {
-            for (x, spot) in row.iter().enumerate() {
-                if *spot == '>' {
-                    if input[y][(x+1)%max_x] == '.' {
-                        next_step[y][(x+1)%max_x] = '>';
-                        next_step[y][x] = '.';
-                        moved = true;
-                    }
-                }
+// The east facing herd moves first, then the south facing one, true if any sea cucumber moved
+fn move_herds(input: &mut InputType) -> bool {
+    let mut moved = false;
+    let mut next_step = input.clone();
+    for ((y, x), spot) in input.iter() {
+        if *spot == '>' {
+            let east = input.wrapping_step((y, x), EAST);
+            if input[east] == '.' {
+                next_step[east] = '>';
+                next_step[(y, x)] = '.';
+                moved = true;
             }
         }
-        input = next_step.clone();
-        for (y, row) in next_step.iter().enumerate() {
-            for (x, spot) in row.iter().enumerate() {
-                if *spot == 'v' {
-                    if next_step[(y+1) % max_y][x] == '.' {
-                        input[(y+1) % max_y][x] = 'v';
-                        input[y][x] = '.';
-                        moved = true;
-                    }
-                }
+    }
+    *input = next_step.clone();
+    for ((y, x), spot) in next_step.iter() {
+        if *spot == 'v' {
+            let south = next_step.wrapping_step((y, x), SOUTH);
+            if next_step[south] == '.' {
+                input[south] = 'v';
+                input[(y, x)] = '.';
+                moved = true;
             }
         }
+    }
+    moved
+}
+
+fn part1(input: &InputType) -> Result<u64> {
+    let mut input = input.clone();
+
+    let mut rounds = 0;
+    let mut moved = true;
+    while moved {
+        moved = move_herds(&mut input);
 
         rounds += 1;
+        if rounds == MAX_ROUNDS && moved {
+            return Err(AocError::unsolvable(format!("the sea cucumbers are still moving after {} steps", MAX_ROUNDS)));
+        }
     }
-    rounds
+    Ok(rounds)
 }
 
 
 fn part2(_input: &InputType) -> u64 {
     0
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn day25_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(353, part1(&input));
-    }
-
-    #[test]
-    fn day25_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(0, part2(&input));
-    }
-}
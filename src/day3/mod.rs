@@ -1,106 +1,157 @@
-use aoc_downloader::download_day;
+use crate::error::{AocError, Result};
+use crate::utils::{input::InputReader, parse};
+use crate::solution::{Answer, Solution};
 
-const DAY: u32 = 3;
-type InputType = u32;
+pub(crate) const DAY: u32 = 3;
+// Every value needs a counter, which stops being cheap somewhere past this
+const MAX_WIDTH: usize = 20;
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
+// How often every number appears, so the report takes the same memory however long it is
+#[derive(Debug, Default)]
+pub(crate) struct Report {
+    width: usize,
+    counts: Vec<usize>,
 }
 
-fn parse_input(input: &str) -> Vec<InputType> {
-    input.lines()
-        .filter(|line| "" != *line)
-        .map(|s| u32::from_str_radix(s, 2).unwrap())
-        .collect::<Vec<_>>()
+impl Report {
+    fn add(&mut self, line: &str) -> Result<()> {
+        if line.len() > MAX_WIDTH {
+            return Err(AocError::parse(MAX_WIDTH + 1, format!("numbers have at most {} bits, got {}", MAX_WIDTH, line.len())));
+        }
+        // Bit by bit, unlike from_str_radix this rejects a leading '+'
+        if let Some((idx, c)) = line.char_indices().find(|&(_, c)| c != '0' && c != '1') {
+            return Err(AocError::parse(idx + 1, format!("expected a binary number, got '{}' in '{}'", c, line)));
+        }
+        let number = line.bytes().fold(0, |number, bit| number << 1 | (bit - b'0') as usize);
+        if line.len() > self.width {
+            self.width = line.len();
+            self.counts.resize(1 << self.width, 0);
+        }
+        self.counts[number] += 1;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    // How many of the numbers have bit set
+    fn ones(&self, bit: usize) -> usize {
+        self.counts.iter()
+            .enumerate()
+            .filter(|(number, _)| bit_is_set(*number, bit))
+            .map(|(_, count)| count)
+            .sum()
+    }
 }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
+fn parse_input(input: &str) -> Result<Report> {
+    let mut report = Report::default();
+    parse::lines(input, |line| report.add(line))?;
+    Ok(report)
 }
 
-fn bit_is_set(num: u32, bit: usize) -> bool {
+pub(crate) struct Day3;
+
+impl Solution for Day3 {
+    type Input = Report;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        part2(input).map(Answer::from)
+    }
+
+    fn stream(&self, input: InputReader) -> Option<Result<(Answer, Answer)>> {
+        let mut report = Report::default();
+        let added: Result<()> = parse::stream(input.lines(), |line| report.add(line)).collect();
+        Some(added.and_then(|_| Ok((part1(&report).into(), part2(&report)?.into()))))
+    }
+}
+
+fn bit_is_set(num: usize, bit: usize) -> bool {
     0 != (num & (1 << bit))
 }
 
-fn part1(input: &Vec<InputType>) -> u32 {
-    let num_len = u32::BITS as usize;
-    let mut gamma = vec![0; num_len];
-    for num in input {
-        for bit in 0..num_len {
-            if bit_is_set(*num, bit) {
-                gamma[bit] += 1;
-            }
-        }
-    }
+fn part1(input: &Report) -> usize {
     let mut gamma_val = 0;
-    for (bit, value) in gamma.iter().enumerate() {
-        if *value > (input.len() / 2) {
-            gamma_val = gamma_val | 1 << bit;
+    for bit in 0..input.width {
+        if input.ones(bit) > (input.len() / 2) {
+            gamma_val |= 1 << bit;
         }
     }
-    let mask = 0b111111111111; // Determined by method of very sharp looking
+    let mask = (1 << input.width) - 1;
     (gamma_val ^ mask) * gamma_val
 }
 
-fn part2(numbers: &Vec<InputType>) -> u32 {
-    let more_of = get_dominant(numbers, 12, one_dominant);
-    let less_of = get_dominant(numbers, 12, zero_dominant);
+fn part2(numbers: &Report) -> Result<usize> {
+    let more_of = get_dominant(numbers, one_dominant)?;
+    let less_of = get_dominant(numbers, zero_dominant)?;
 
-    more_of * less_of
+    Ok(more_of * less_of)
 }
 
-fn one_dominant(ones: u32, zeros: u32) -> bool {
+fn one_dominant(ones: usize, zeros: usize) -> bool {
     ones >= zeros
 }
 
-fn zero_dominant(ones: u32, zeros: u32) -> bool {
+fn zero_dominant(ones: usize, zeros: usize) -> bool {
     zeros > ones
 }
 
-
-fn get_dominant(numbers: &Vec<InputType>, num_len: usize, compare: fn(u32, u32) -> bool) -> InputType {
-    let mut numbers = numbers.clone();
-    let mut current_bit = num_len;
-    while 1 != numbers.len() {
-        current_bit -= 1;
-        let mut zeros = 0;
-        let mut ones = 0;
-        for number in &numbers {
-            if bit_is_set(*number, current_bit) {
-                ones += 1;
-            } else {
-                zeros += 1;
-            }
+// The numbers left after filtering by the bits above current_bit are the ones in low..high
+fn get_dominant(numbers: &Report, compare: fn(usize, usize) -> bool) -> Result<usize> {
+    let (mut low, mut high) = (0, numbers.counts.len());
+    let mut current_bit = numbers.width;
+    loop {
+        let left: usize = numbers.counts[low..high].iter().sum();
+        if left == 1 {
+            return Ok(low + numbers.counts[low..high].iter().position(|&count| count == 1).unwrap());
+        }
+        if left == 0 || current_bit == 0 {
+            return Err(AocError::unsolvable("the bit criteria do not narrow the report down to one number"));
         }
-        if compare(ones, zeros) {
-            numbers = numbers.clone().into_iter()
-                .filter(|n| bit_is_set(*n, current_bit))
-                .collect();
+        current_bit -= 1;
+        let middle = low + (1 << current_bit);
+        let zeros: usize = numbers.counts[low..middle].iter().sum();
+        let ones: usize = numbers.counts[middle..high].iter().sum();
+        // A bit all of the numbers share rules none of them out, whatever the criteria say
+        if zeros == 0 || (ones != 0 && compare(ones, zeros)) {
+            low = middle;
         } else {
-            numbers = numbers.clone().into_iter()
-                .filter(|n| !bit_is_set(*n, current_bit))
-                .collect();
+            high = middle;
         }
     }
-    numbers[0]
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010\n";
+
     #[test]
-    fn day3_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(1025636, part1(&input));
+    fn day3_testcase1() {
+        let input = parse_input(EXAMPLE).unwrap();
+        assert_eq!(198, part1(&input));
+        assert_eq!(230, part2(&input).unwrap());
+
+        let reader = InputReader::from_string(EXAMPLE.to_string());
+        assert_eq!((Answer::Number(198), Answer::Number(230)), Day3.stream(reader).unwrap().unwrap());
     }
 
     #[test]
-    fn day3_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(793873, part2(&input));
+    fn day3_rejects_ambiguous_reports() {
+        assert!(part2(&parse_input("101\n101\n").unwrap()).is_err());
+        assert_eq!(5 * 7, part2(&parse_input("101\n111\n").unwrap()).unwrap());
+        assert!(matches!(parse_input("10\n102\n"), Err(AocError::Parse { line: 2, column: 3, .. })));
+        assert!(matches!(parse_input("101\n+101\n"), Err(AocError::Parse { line: 2, column: 1, .. })));
+        assert!(parse_input(&"1".repeat(MAX_WIDTH + 1)).is_err());
     }
 }
@@ -1,21 +1,27 @@
 use std::str::FromStr;
-use aoc_downloader::download_day;
-use regex::Regex;
+use itertools::Itertools;
 
-use crate::utils::table::Table;
+use crate::utils::{parse, grid::Grid};
+use crate::error::{AocError, Result};
+use crate::solution::{Answer, Solution};
 
-const DAY: u32 = 4;
-type InputType = String;
+pub(crate) const DAY: u32 = 4;
+type InputType = Bingo;
 
-#[derive(Debug)]
+pub(crate) struct Bingo {
+    numbers: Vec<i32>,
+    boards: Vec<Board>,
+}
+
+#[derive(Clone, Debug)]
 struct Board {
-    board: Table<i32>,
+    board: Grid<i32>,
     winning_number: i32,
 }
 
 impl Board {
     pub fn get_score(&self) -> i32 {
-        self.board.get_vector()
+        self.board.cells()
             .iter()
             .filter(|&entry| -1 != *entry)
             .sum()
@@ -32,7 +38,7 @@ impl Board {
     }
 
     fn mark_number(&mut self, number: i32) {
-        let called_number = self.board.get_vetor_mut()
+        let called_number = self.board.cells_mut()
             .iter_mut()
             .find(|entry| number == **entry);
         if called_number.is_some() {
@@ -45,98 +51,96 @@ impl Board {
     }
 
     fn check_row(&self) -> bool {
-        self.board.get_rows()
-            .iter()
+        self.board.rows()
             .any(|row| row.iter()
                 .all(|&value| -1 == value))
     }
 
     fn check_column(&self) -> bool {
-        self.board.get_columns()
-            .iter()
-            .any(|column| column.iter()
+        self.board.columns()
+            .any(|mut column| column
                 .all(|&value| -1 == value))
     }
 }
 
 impl FromStr for Board {
-    type Err = std::num::ParseIntError;
-
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(r" ").unwrap();
-            static ref RE2: Regex = Regex::new(r"\s+").unwrap();
+    type Err = AocError;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let board = parse::lines(input, |line| {
+            let row = parse::words(line)
+                .map(|(column, n)| parse::number(n, column))
+                .collect::<Result<Vec<i32>>>()?;
+            if row.len() != 5 {
+                return Err(AocError::parse(1, format!("expected 5 numbers in a board row, got {}", row.len())));
+            }
+            Ok(row)
+        })?;
+        if board.len() != 5 {
+            return Err(AocError::parse(1, format!("expected 5 rows in a board, got {}", board.len())));
         }
-        let board = input.lines()
-            .filter(|line| "" != *line)
-            .map(|line| RE2.replace_all(line.trim(), " "))
-            .map(|line| RE.split(&line).map(|n| n.parse::<i32>().unwrap()).collect::<Vec<_>>())
-            .collect::<Vec<_>>();
         Ok(Board {
-            board: Table::from_vecvec(board),
+            board: Grid::from_vecvec(board),
             winning_number: -1,
         })
     }
 }
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
-}
+fn parse_input(input: &str) -> Result<InputType> {
+    let lines = input.lines()
+        .enumerate()
+        .filter(|(_, line)| "" != *line)
+        .collect::<Vec<_>>();
+    let (idx, numbers) = lines.first()
+        .ok_or_else(|| AocError::parse(1, "expected the drawn numbers"))?;
+    let numbers = parse::numbers(numbers, ',').map_err(|error| error.at_line(idx + 1))?;
+
+    let boards = lines[1..].chunks(5)
+        .map(|board| {
+            let board_setup = board.iter().map(|(_, line)| line).join("\n");
+            Board::from_str(&board_setup).map_err(|error| error.at_line(board[0].0 + 1))
+        })
+        .collect::<Result<Vec<_>>>()?;
 
-fn parse_input(input: &str) -> Vec<InputType> {
-    input.lines()
-        .filter(|line| "" != *line)
-        .map(|s| s.to_owned())
-        .collect::<Vec<_>>()
+    Ok(Bingo { numbers, boards })
 }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
-}
+pub(crate) struct Day4;
 
-fn get_numbers(input: &str) -> Vec<i32> {
-    let re = Regex::new(r",").unwrap();
-    re.split(&input)
-        .map(|v| v.parse::<i32>().unwrap())
-        .collect::<Vec<_>>()
-}
+impl Solution for Day4 {
+    type Input = InputType;
 
-fn build_boards(input: &Vec<InputType>) -> Vec<Board> {
-    let mut boards = Vec::new();
-    for board in input[1..].chunks(5) {
-        let mut board_setup = String::new();
-        for board_line in board {
-            board_setup.push_str(&board_line);
-            board_setup.push_str("\n");
-        }
-        boards.push(Board::from_str(&board_setup).unwrap());
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        part2(input).map(Answer::from)
     }
-    boards
 }
 
-fn part1(input: &Vec<InputType>) -> u32{
-    let numbers = get_numbers(&input[0]);
-    let mut boards = build_boards(input);
+fn part1(input: &InputType) -> Result<u32> {
+    let mut boards = input.boards.clone();
 
-    for number in numbers {
+    for &number in &input.numbers {
         for board in boards.iter_mut() {
             if board.play_game(number) {
-                return (board.get_score() * number) as u32;
+                return Ok((board.get_score() * number) as u32);
             }
         }
     }
-    0
+    Err(AocError::unsolvable("no board wins"))
 }
 
-fn part2(input: &Vec<InputType>) -> u32 {
-    let numbers = get_numbers(&input[0]);
-    let mut boards = build_boards(input);
+fn part2(input: &InputType) -> Result<u32> {
+    let mut boards = input.boards.clone();
 
     let mut winning_number = -1;
-    for number in numbers {
+    for &number in &input.numbers {
         for board in boards.iter_mut() {
             if !board.has_won() {
                 board.play_game(number);
@@ -147,25 +151,8 @@ fn part2(input: &Vec<InputType>) -> u32 {
             break;
         }
     }
-    (boards.into_iter()
+    let last_board = boards.into_iter()
         .find(|board| &winning_number == &board.get_winning_number())
-        .unwrap()
-        .get_score() * winning_number) as u32
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn day4_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(41668, part1(&input));
-    }
-
-    #[test]
-    fn day4_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(10478, part2(&input));
-    }
+        .ok_or_else(|| AocError::unsolvable("not every board wins"))?;
+    Ok((last_board.get_score() * winning_number) as u32)
 }
@@ -1,100 +1,84 @@
 use std::str::FromStr;
-use aoc_downloader::download_day;
 use regex::Regex;
 
-use crate::utils::{table::Table, coordinates::Vector};
+use crate::utils::{parse, coordinates::Vector, sparse::SparseGrid};
+use crate::error::{AocError, Result};
+use crate::solution::{Answer, Solution};
 
-const DAY: u32 = 5;
+pub(crate) const DAY: u32 = 5;
 type InputType = Lines;
 
 #[derive(Debug)]
-struct Lines {
-    start: Vector<usize>,
-    end: Vector<usize>,
+pub(crate) struct Lines {
+    start: Vector<isize, 2>,
+    end: Vector<isize, 2>,
 }
 
 impl FromStr for Lines {
-    type Err = std::num::ParseIntError;
-    
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
+    type Err = AocError;
+
+    fn from_str(input: &str) -> Result<Self> {
         lazy_static!{
-            static ref RE: Regex = Regex::new(r"(\d+),(\d+) -> (\d+),(\d+)").unwrap();
+            static ref RE: Regex = Regex::new(r"^(\d+),(\d+) -> (\d+),(\d+)$").unwrap();
+        }
+        let captured = parse::captures(&RE, input, "'x1,y1 -> x2,y2'")?;
+        let coordinate = |idx: usize| -> Result<isize> { parse::capture(&captured, idx) };
+        let (x1, y1, x2, y2) = (coordinate(1)?, coordinate(2)?, coordinate(3)?, coordinate(4)?);
+        if x1 != x2 && y1 != y2 && x1.abs_diff(x2) != y1.abs_diff(y2) {
+            return Err(AocError::parse(1, "lines have to be horizontal, vertical or diagonal"));
         }
-        Ok(RE.captures(input).and_then(|captured| {
-            Some(Lines {
-                start: Vector::from((captured[1].parse::<usize>().unwrap(), captured[2].parse::<usize>().unwrap())),
-                end: Vector::from((captured[3].parse::<usize>().unwrap(), captured[4].parse::<usize>().unwrap())),
-            })
-        }).unwrap())
+        Ok(Lines {
+            start: Vector::from((x1, y1)),
+            end: Vector::from((x2, y2)),
+        })
     }
 }
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
+fn parse_input(input: &str) -> Result<Vec<InputType>> {
+    parse::lines(input, Lines::from_str)
 }
 
-fn parse_input(input: &str) -> Vec<InputType> {
-    input.lines()
-        .filter(|line| "" != *line)
-        .map(|s| Lines::from_str(s).unwrap())
-        .collect::<Vec<_>>()
-}
+pub(crate) struct Day5;
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
+impl Solution for Day5 {
+    type Input = Vec<InputType>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
 }
 
 fn part1(input: &Vec<InputType>) -> u32{
-    let mut seafloor = Table::from_vecvec(vec![vec![0; 1000]; 1000]); // Determined by method of very sharp looking
-    for line in input {
-        let vector = (&line.start - &line.end).normalized();
-        let mut current_pos = line.start.clone();
-        if !include_diagonals(&vector) {
-            continue;
-        }
-        seafloor[&current_pos] += 1;
-        while &line.end != &current_pos {
-            current_pos = &current_pos + &vector;
-            seafloor[&current_pos] += 1;
-        }
-    }
-    seafloor.get_vector().iter().filter(|&n| 1 < *n).count() as u32
+    let straight = input.iter().filter(|line| !is_diagonal(line.end - line.start));
+    count_overlaps(straight)
 }
 
-fn include_diagonals(vector: &Vector<isize>) -> bool {
-    !((vector['x'] != 0) && (vector['y'] != 0))
+fn is_diagonal(direction: Vector<isize, 2>) -> bool {
+    direction.x() != 0 && direction.y() != 0
 }
 
 fn part2(input: &Vec<InputType>) -> u32 {
-    let mut seafloor = Table::from_vecvec(vec![vec![0; 1000]; 1000]);
-    for line in input {
-        let mut current_pos = line.start.clone();
-        let vector = (&line.start - &line.end).normalized();
-        seafloor[&current_pos] += 1;
-        while &line.end != &current_pos {
-            current_pos = &current_pos + &vector;
-            seafloor[&current_pos] += 1;
-        }
-    }
-    seafloor.get_vector().iter().filter(|&n| 1 < *n).count() as u32
+    count_overlaps(input.iter())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn day5_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(5145, part1(&input));
-    }
-
-    #[test]
-    fn day5_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(16518, part2(&input));
+fn count_overlaps<'a>(lines: impl Iterator<Item = &'a Lines>) -> u32 {
+    let mut seafloor = SparseGrid::new(0);
+    for line in lines {
+        let direction = (line.end - line.start).signum();
+        let mut current_pos = line.start;
+        seafloor[current_pos] += 1;
+        while line.end != current_pos {
+            current_pos += direction;
+            seafloor[current_pos] += 1;
+        }
     }
+    seafloor.values().filter(|&n| 1 < *n).count() as u32
 }
@@ -1,66 +1,119 @@
-use aoc_downloader::download_day;
+use crate::error::{AocError, Result};
+use crate::utils::{input::InputReader, parse};
+use crate::solution::{Answer, Solution};
 
-const DAY: u32 = 6;
+pub(crate) const DAY: u32 = 6;
 type InputType = u32;
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
+fn parse_input(input: &str) -> Result<Vec<InputType>> {
+    let line = input.trim_end();
+    parse::fields(line, ',')
+        .map(|(column, field)| parse_timer(field).map_err(|error| error.at_column(column)))
+        .collect()
 }
 
-fn parse_input(input: &str) -> Vec<InputType> {
-    let len = input.len();
-    let mut input = input.to_owned();
-    input.truncate(len - 1);
-    input
-        .split(',')
-        .map(|s| s.parse::<u32>().unwrap())
-        .collect::<Vec<_>>()
+fn parse_timer(field: &str) -> Result<InputType> {
+    let timer = parse::number(field, 1)?;
+    if timer > 8 {
+        return Err(AocError::parse(1, format!("timers only go up to 8, got {}", timer)));
+    }
+    Ok(timer)
 }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
-}
+pub(crate) struct Day6;
 
-fn part1(input: &Vec<InputType>) -> u32{
-    let mut lifetime = vec![0; 9];
-    for fish in input {
-        lifetime[*fish as usize] += 1;
+impl Solution for Day6 {
+    type Input = Vec<InputType>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
     }
-    for _ in 0..80{
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
+
+    // The input is one line, so it is read field by field instead
+    fn stream(&self, input: InputReader) -> Option<Result<(Answer, Answer)>> {
+        let mut lifetime = [0; 9];
+        let mut column = 1;
+        for field in input.split(b',') {
+            let field = match field {
+                Ok(field) => field,
+                Err(error) => return Some(Err(error)),
+            };
+            match parse_timer(field.trim_end()) {
+                Ok(timer) => lifetime[timer as usize] += 1,
+                Err(error) => return Some(Err(error.at_column(column))),
+            }
+            column += field.len() + 1;
+        }
+        Some(Ok((breed(lifetime, 80).into(), breed(lifetime, 256).into())))
+    }
+}
+
+// Fish by the days left on their timer, after the given number of days
+fn breed(mut lifetime: [u64; 9], days: usize) -> u64 {
+    for _ in 0..days {
         lifetime.rotate_left(1);
         lifetime[6] += lifetime[8];
     }
     lifetime.iter().sum()
 }
 
-fn part2(input: &Vec<InputType>) -> u64 {
-    let mut lifetime:Vec<u64> = vec![0; 9];
+fn lifetimes(input: &Vec<InputType>) -> [u64; 9] {
+    let mut lifetime = [0; 9];
     for fish in input {
         lifetime[*fish as usize] += 1;
     }
-    for _ in 0..256 {
-        lifetime.rotate_left(1);
-        lifetime[6] += lifetime[8];
-    }
-    lifetime.iter().sum()
+    lifetime
+}
+
+fn part1(input: &Vec<InputType>) -> u64 {
+    breed(lifetimes(input), 80)
+}
+
+fn part2(input: &Vec<InputType>) -> u64 {
+    breed(lifetimes(input), 256)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::gen;
+
+    // Every fish on its own, as the puzzle describes them
+    fn simulate(mut fish: Vec<InputType>, days: usize) -> u64 {
+        for _ in 0..days {
+            let spawned = fish.iter().filter(|&&timer| timer == 0).count();
+            for timer in &mut fish {
+                *timer = if *timer == 0 { 6 } else { *timer - 1 };
+            }
+            fish.extend(std::iter::repeat_n(8, spawned));
+        }
+        fish.len() as u64
+    }
 
     #[test]
-    fn day6_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(359999, part1(&input));
+    fn day6_buckets_match_simulation() {
+        gen::check(200, |rng| {
+            let fish: Vec<InputType> = (0..rng.range(1..=10)).map(|_| rng.range(0..=8) as InputType).collect();
+            let days = rng.below(60);
+            assert_eq!(simulate(fish.clone(), days), breed(lifetimes(&fish), days), "{:?} after {} days", fish, days);
+        });
     }
 
     #[test]
-    fn day6_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(1631647919273, part2(&input));
+    fn day6_streams_both_parts() {
+        let reader = InputReader::from_string("3,4,3,1,2\n".to_string());
+        assert_eq!((Answer::Number(5934), Answer::Number(26984457539)), Day6.stream(reader).unwrap().unwrap());
+
+        let error = Day6.stream(InputReader::from_string("3,4,9\n".to_string())).unwrap().unwrap_err();
+        assert!(matches!(error, AocError::Parse { line: 1, column: 5, .. }), "{}", error);
+        assert!(matches!(parse_input("3,x"), Err(AocError::Parse { column: 3, .. })));
     }
 }
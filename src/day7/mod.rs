@@ -1,27 +1,30 @@
-use aoc_downloader::download_day;
+use crate::error::Result;
+use crate::utils::parse;
+use crate::solution::{Answer, Solution};
 
-const DAY: u32 = 7;
+pub(crate) const DAY: u32 = 7;
 type InputType = i32;
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
+fn parse_input(input: &str) -> Result<Vec<InputType>> {
+    parse::numbers(input.trim_end(), ',')
 }
 
-fn parse_input(input: &str) -> Vec<InputType> {
-    let len = input.len();
-    let mut input = input.to_owned();
-    input.truncate(len - 1);
-    input
-        .split(',')
-        .map(|s| s.parse::<i32>().unwrap())
-        .collect::<Vec<_>>()
-}
+pub(crate) struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<InputType>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
 }
 
 fn part1(input: &Vec<InputType>) -> i32{
@@ -40,25 +43,35 @@ fn gauss(input: i32) -> i32 {
 }
 
 fn part2(input: &Vec<InputType>) -> i32 {
-    let average: i32 = input.iter().sum::<i32>() / input.len() as i32;
-    // This is not stable for general input, but seems to work reliably
-    // for AoC 2021 input
-    input.iter().map(|&crab| gauss((average - crab).abs())).sum::<i32>()
+    // The best position is within half a step of the average, so it's
+    // either the average rounded down or rounded up
+    let average = input.iter().sum::<i32>().div_euclid(input.len() as i32);
+    (average..=average + 1)
+        .map(|position| input.iter().map(|&crab| gauss((position - crab).abs())).sum::<i32>())
+        .min()
+        .unwrap()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::gen;
 
-    #[test]
-    fn day7_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(342534, part1(&input));
+    // Tries every position between the outermost crabs
+    fn cheapest(input: &[InputType], cost: fn(i32) -> i32) -> i32 {
+        let (min, max) = (*input.iter().min().unwrap(), *input.iter().max().unwrap());
+        (min..=max)
+            .map(|position| input.iter().map(|&crab| cost((position - crab).abs())).sum::<i32>())
+            .min()
+            .unwrap()
     }
 
     #[test]
-    fn day7_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(94004208, part2(&input));
+    fn day7_shortcuts_match_exhaustive_search() {
+        gen::check(500, |rng| {
+            let input: Vec<i32> = (0..rng.range(1..=12)).map(|_| rng.range(0..=30) as i32).collect();
+            assert_eq!(cheapest(&input, |distance| distance), part1(&input), "{:?}", input);
+            assert_eq!(cheapest(&input, gauss), part2(&input), "{:?}", input);
+        });
     }
 }
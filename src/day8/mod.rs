@@ -1,34 +1,62 @@
-use aoc_downloader::download_day;
 use std::collections::HashMap;
 
-const DAY: u32 = 8;
+use crate::error::{AocError, Result};
+use crate::utils::parse;
+use crate::solution::{Answer, Solution};
+
+pub(crate) const DAY: u32 = 8;
 type InputType = Input;
 
-struct Input {
+pub(crate) struct Input {
     input: Vec<String>,
     output: Vec<String>,
 }
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
+// Column is where patterns starts in its line
+fn parse_patterns(patterns: &str, column: usize, expected: usize) -> Result<Vec<String>> {
+    let patterns = parse::words(patterns).collect::<Vec<_>>();
+    if patterns.len() != expected {
+        return Err(AocError::parse(1, format!("expected {} patterns on each side of '|', got {}", expected, patterns.len())));
+    }
+    for (start, pattern) in &patterns {
+        if let Some(idx) = pattern.find(|c| !('a'..='g').contains(&c)) {
+            return Err(AocError::parse(column + start - 1 + idx, "segments are named a to g"));
+        }
+    }
+    Ok(patterns.into_iter().map(|(_, pattern)| pattern.to_owned()).collect())
 }
 
-fn parse_input(input: &str) -> Vec<InputType> {
-    input.lines()
-        .map(|line| {
-            let inp: Vec<String> = line.split('|').map(|inp| inp.to_owned()).collect();
-            Input {
-                input: inp[0].split(' ').map(|inp2| inp2.to_owned()).collect::<Vec<String>>(),
-                output: inp[1].split(' ').map(|inp2| inp2.to_owned()).collect::<Vec<String>>(),
-            }})
-        .collect()
+fn parse_input(input: &str) -> Result<Vec<InputType>> {
+    parse::lines(input, |line| {
+        let (input, output) = line.split_once('|')
+            .ok_or_else(|| AocError::parse(1, "expected '<ten patterns> | <four digits>'"))?;
+        Ok(Input {
+            input: parse_patterns(input, 1, 10)?,
+            output: parse_patterns(output, input.len() + 2, 4)?,
+        })
+    })
 }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
+fn display_mismatch() -> AocError {
+    AocError::unsolvable("the signal patterns do not fit a seven segment display")
+}
+
+pub(crate) struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<InputType>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        part2(input).map(Answer::from)
+    }
 }
 
 fn part1(input: &Vec<InputType>) -> u32{
@@ -44,20 +72,20 @@ fn part1(input: &Vec<InputType>) -> u32{
     count
 }
 
-fn match_index(input: char) -> usize {
+fn match_index(input: char, pattern: &str) -> Result<usize> {
     match input {
-        'a' => 0,
-        'b' => 1,
-        'c' => 2,
-        'd' => 3,
-        'e' => 4,
-        'f' => 5,
-        'g' => 6,
-        _ => panic!(),
+        'a' => Ok(0),
+        'b' => Ok(1),
+        'c' => Ok(2),
+        'd' => Ok(3),
+        'e' => Ok(4),
+        'f' => Ok(5),
+        'g' => Ok(6),
+        _ => Err(AocError::unsolvable(format!("pattern {} is wired to segment '{}', which does not exist", pattern, input))),
     }
 }
 
-fn segements_to_digit(input: &str, mapping: &HashMap<char, char>) -> u32 {
+fn segements_to_digit(input: &str, mapping: &HashMap<char, char>) -> Result<u32> {
     lazy_static! {
         static ref NUMBERS: Vec<Vec<bool>> = vec![
             vec![true,  true, true, false, true, true, true], // 0
@@ -74,9 +102,12 @@ fn segements_to_digit(input: &str, mapping: &HashMap<char, char>) -> u32 {
     }
     let mut segments = vec![false; 7];
     for segment in input.chars() {
-        segments[match_index(mapping[&segment])] = true;
+        segments[match_index(*mapping.get(&segment).ok_or_else(display_mismatch)?, input)?] = true;
     }
-    NUMBERS.iter().position(|number| *number == segments).unwrap() as u32
+    NUMBERS.iter()
+        .position(|number| *number == segments)
+        .map(|digit| digit as u32)
+        .ok_or_else(display_mismatch)
 }
 
 fn get_c_and_f(current: &String, inputs: &Vec<String>, mapping: &mut HashMap<char, char>) {
@@ -96,20 +127,21 @@ fn get_c_and_f(current: &String, inputs: &Vec<String>, mapping: &mut HashMap<cha
     }
 }
 
-fn get_a(current: &String, mapping: &mut HashMap<char, char>) {
+fn get_a(current: &String, mapping: &mut HashMap<char, char>) -> Result<()> {
     let segments: Vec<char> = current.chars().collect();
-    let a = segments.iter().find(|segment| !mapping.contains_key(segment)).unwrap();
+    let a = segments.iter().find(|segment| !mapping.contains_key(segment)).ok_or_else(display_mismatch)?;
     mapping.insert(*a, 'a');
+    Ok(())
 }
 
-fn get_b_and_d(current: &String, inputs: &Vec<String>, mapping: &mut HashMap<char, char>) {
+fn get_b_and_d(current: &String, inputs: &Vec<String>, mapping: &mut HashMap<char, char>) -> Result<()> {
     let segments: Vec<char> = current.chars().collect();
     let possible_b = segments.iter()
         .find(|segment| !mapping.contains_key(segment))
-        .unwrap();
+        .ok_or_else(display_mismatch)?;
     let possible_d = segments.iter()
         .find(|segment| !mapping.contains_key(segment) && *segment != possible_b)
-        .unwrap();
+        .ok_or_else(display_mismatch)?;
     let mut count_b = 0;
     for number in inputs {
         if number.chars().any(|c| c == *possible_b) {
@@ -123,16 +155,17 @@ fn get_b_and_d(current: &String, inputs: &Vec<String>, mapping: &mut HashMap<cha
         mapping.insert(*possible_b, 'd');
         mapping.insert(*possible_d, 'b');
     }
+    Ok(())
 }
 
-fn get_e_and_g(current: &String, inputs: &Vec<String>, mapping: &mut HashMap<char, char>) {
+fn get_e_and_g(current: &String, inputs: &Vec<String>, mapping: &mut HashMap<char, char>) -> Result<()> {
     let segments: Vec<char> = current.chars().collect();
     let possible_e = segments.iter()
         .find(|segment| !mapping.contains_key(segment))
-        .unwrap();
+        .ok_or_else(display_mismatch)?;
     let possible_g = segments.iter()
         .find(|segment| !mapping.contains_key(segment) && *segment != possible_e)
-        .unwrap();
+        .ok_or_else(display_mismatch)?;
     let mut count_b = 0;
     for number in inputs {
         if number.chars().any(|c| c == *possible_e) {
@@ -146,32 +179,27 @@ fn get_e_and_g(current: &String, inputs: &Vec<String>, mapping: &mut HashMap<cha
         mapping.insert(*possible_e, 'g');
         mapping.insert(*possible_g, 'e');
     }
+    Ok(())
 }
 
-fn part2(input: &Vec<InputType>) -> u32 {
+fn part2(input: &Vec<InputType>) -> Result<u32> {
     let mut total_sum = 0;
     for inp in input {
         let mut matches = HashMap::<char, char>::new();
-        let one = inp.input.iter().find(|number| 2 == number.len()).unwrap();
-        get_c_and_f(&one, &inp.input, &mut matches);
-        let seven = inp.input.iter().find(|number| 3 == number.len()).unwrap();
-        get_a(&seven, &mut matches);
-        let four = inp.input.iter().find(|number| 4 == number.len()).unwrap();
-        get_b_and_d(&four, &inp.input, &mut matches);
-        let eight = inp.input.iter().find(|number| 7 == number.len()).unwrap();
-        get_e_and_g(&eight, &inp.input, &mut matches);
+        let with_segments = |count| inp.input.iter().find(|number| count == number.len()).ok_or_else(display_mismatch);
+        get_c_and_f(with_segments(2)?, &inp.input, &mut matches);
+        get_a(with_segments(3)?, &mut matches)?;
+        get_b_and_d(with_segments(4)?, &inp.input, &mut matches)?;
+        get_e_and_g(with_segments(7)?, &inp.input, &mut matches)?;
 
         let mut line_sum = 0;
         for out in &inp.output {
-            if out == "" {
-                continue;
-            }
-            line_sum = line_sum * 10 + segements_to_digit(&out, &matches);
+            line_sum = line_sum * 10 + segements_to_digit(&out, &matches)?;
         }
         total_sum += line_sum;
     }
 
-    total_sum
+    Ok(total_sum)
 }
 
 #[cfg(test)]
@@ -179,14 +207,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn day8_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(342, part1(&input));
-    }
-
-    #[test]
-    fn day8_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(1068933, part2(&input));
+    fn day8_reports_wiring_to_unknown_segments() {
+        let mapping = HashMap::from([('a', 'c'), ('b', 'x')]);
+        let error = segements_to_digit("ab", &mapping).unwrap_err();
+        assert!(matches!(error, AocError::Unsolvable(ref message) if message.contains("ab")), "{}", error);
     }
 }
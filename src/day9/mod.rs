@@ -1,125 +1,83 @@
-use aoc_downloader::download_day;
-use std::collections::HashSet;
+use std::iter;
 
-const DAY: u32 = 9;
-type InputType = Vec<u32>;
+use crate::error::{AocError, Result};
+use crate::utils::{grid::{Grid, Position}, image::{self, REGIONS}, search};
+use crate::solution::{Answer, Images, Solution};
 
-fn get_input() -> String {
-    download_day((DAY) as u32, "input").unwrap();
-    std::fs::read_to_string(format!("input/input{}.txt", DAY)).unwrap()
-}
+pub(crate) const DAY: u32 = 9;
+type InputType = Grid<u32>;
 
-fn parse_input(input: &str) -> Vec<InputType> {
-    input.lines()
-        .map(|line| line.chars().
-            map(|c| c.to_digit(10).unwrap())
-            .collect::<Vec<u32>>())
-        .collect::<Vec<_>>()
+fn parse_input(input: &str) -> Result<InputType> {
+    Grid::parse_digits(input)
 }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(&input);
-    println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
-}
+pub(crate) struct Day9;
+
+impl Solution for Day9 {
+    type Input = InputType;
 
-fn get_neighbours(current_pos: (usize, usize), input: &Vec<InputType>) -> Vec<(usize, usize)> {
-    lazy_static!{
-        static ref OFFSETS: Vec<(isize, isize)> = vec![
-            (0, 1), (1, 0), (0, -1), (-1, 0),
-        ];
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
     }
-    let max_x = input[0].len() as isize;
-    let max_y = input.len() as isize;
-    let mut neighbours = vec![];
-    for offset in OFFSETS.iter() {
-        let new_pos = (current_pos.0 as isize + offset.0,
-            current_pos.1 as isize + offset.1);
-        if new_pos.0 < 0 || new_pos.0 >= max_x || new_pos.1 < 0 || new_pos.1 >= max_y {
-            continue;
-        }
-        neighbours.push((new_pos.0 as usize, new_pos.1 as usize));
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        part2(input).map(Answer::from)
     }
 
-    neighbours
+    // The height map, then one more basin filled in every frame
+    fn images(&self, input: Self::Input) -> Option<Images> {
+        let mut canvas = input.map(|&height| image::grey(height, 9));
+        let first = canvas.clone();
+        let minima = get_mimima(&input);
+        Some(Box::new(iter::once(first).chain(minima.into_iter().enumerate().map(move |(idx, minimum)| {
+            for position in search::flood_fill(minimum, search::grid_steps(&input, |&height| height != 9)).nodes {
+                canvas[position] = REGIONS[idx % REGIONS.len()];
+            }
+            canvas.clone()
+        }))))
+    }
 }
 
-fn get_mimima(input: &Vec<InputType>) -> Vec<(usize, usize)> {
+fn get_mimima(input: &InputType) -> Vec<Position> {
     let mut minima = Vec::new();
-    for (y, row) in input.iter().enumerate() {
-        for (x, element) in row.iter().enumerate() {
-            let neighbours = get_neighbours((x, y), &input);
-            if neighbours.iter().all(|v| input[v.1][v.0] > *element) {
-                minima.push((x, y));
-            }
+    for (position, element) in input.iter() {
+        if input.neighbours4(position).all(|(_, neighbour)| neighbour > element) {
+            minima.push(position);
         }
     }
     minima
 }
 
-fn part1(input: &Vec<InputType>) -> u32{
+fn part1(input: &InputType) -> u32{
     let minima = get_mimima(input);
     let mut risk_level = 0;
     for minimum in minima {
-        risk_level += input[minimum.1][minimum.0] + 1;
+        risk_level += input[minimum] + 1;
     }
     risk_level
 }
 
-fn depth_search(minimum: (usize, usize), map: &Vec<InputType>) -> u32 {
-    let mut queue = vec![minimum];
-    let mut visited = HashSet::new();
-    let mut part = 0;
-
-    while queue.len() > 0 {
-        if let Some(current) = queue.pop() {
-            if visited.contains(&current) {
-                continue;
-            }
-            visited.insert(current);
-
-            let value = map[current.1][current.0];
-            if value != 9 {
-                part += 1;
-            } else {
-                continue;
-            }
-
-            for neighbour in get_neighbours(current, map) {
-                queue.push(neighbour);
-            }
-        }
-    }
-    part
+fn basin_size(minimum: Position, map: &InputType) -> u32 {
+    search::flood_fill(minimum, search::grid_steps(map, |&height| height != 9)).nodes.len() as u32
 }
 
-fn part2(input: &Vec<InputType>) -> u32 {
+fn part2(input: &InputType) -> Result<u32> {
     let minima = get_mimima(input);
 
     let mut sizes = Vec::new();
     for minimum in minima {
-        sizes.push(depth_search(minimum, &input));
+        sizes.push(basin_size(minimum, input));
     }
     
     sizes.sort();
     sizes.reverse();
 
-    sizes[0] * sizes[1] * sizes[2]
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn day9_part1_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(425, part1(&input));
-    }
-
-    #[test]
-    fn day9_part2_output() {
-        let input = parse_input(&get_input());
-        assert_eq!(1135260, part2(&input));
+    if sizes.len() < 3 {
+        return Err(AocError::unsolvable(format!("expected at least three basins, found {}", sizes.len())));
     }
+    Ok(sizes[0] * sizes[1] * sizes[2])
 }
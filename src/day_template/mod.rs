@@ -1,27 +1,29 @@
-use aoc_downloader::download_day;
+use crate::error::Result;
+use crate::utils::parse;
+use crate::solution::{Answer, Solution};
 
-const DAY: u32 = 0;
+pub(crate) const DAY: u32 = 0;
 
-fn get_input() -> Vec<String> {
-    use std::io::BufRead;
-    download_day((DAY) as u32, "input").unwrap();
-
-    let file = std::fs::File::open(format!("input/input{}.txt", DAY)).unwrap();
-    let reader = std::io::BufReader::new(&file);
-    reader.lines().collect::<Result<_, _>>().unwrap()
+fn parse_input(input: &str) -> Result<Vec<u32>> {
+    parse::lines(input, |line| parse::number(line, 1))
 }
 
-fn parse_input(input: Vec<String>) -> Vec<u32> {
-    input
-        .iter()
-        .map(|v| v.parse::<u32>().unwrap())
-        .collect::<Vec<_>>()
-}
+pub(crate) struct Day0;
+
+impl Solution for Day0 {
+    type Input = Vec<u32>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
 
-pub fn run_day() {
-    let input = get_input();
-    let input = parse_input(input);
-    println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
+    fn part2(&self, input: &Self::Input) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
 }
 
 fn part1(input: &Vec<u32>) -> u32 {
@@ -47,20 +49,3 @@ fn part2(input: &Vec<u32>) -> u32 {
     }
     0
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn day0_part1_output() {
-        let input = parse_input(get_input());
-        assert_eq!(744475, part1(&input));
-    }
-
-    #[test]
-    fn day0_part2_output() {
-        let input = parse_input(get_input());
-        assert_eq!(70276940, part2(&input));
-    }
-}
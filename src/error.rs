@@ -0,0 +1,79 @@
+use std::{fmt, io, path::PathBuf};
+
+#[derive(Debug)]
+pub enum AocError {
+    Io(PathBuf, io::Error),
+    Write(PathBuf, io::Error),
+    Encode(PathBuf, String),
+    // A file of ours, such as answers.json, that doesn't hold what it should
+    Format(PathBuf, String),
+    Download(u32, String),
+    Missing(u32, PathBuf),
+    Offline(u32, PathBuf),
+    Profile(String),
+    // Line and column start at 1, a parser that only ever sees one line reports line 1
+    Parse { line: usize, column: usize, message: String },
+    Unsolvable(String),
+}
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+impl AocError {
+    pub fn parse(column: usize, message: impl Into<String>) -> Self {
+        AocError::Parse { line: 1, column, message: message.into() }
+    }
+
+    pub fn unsolvable(message: impl Into<String>) -> Self {
+        AocError::Unsolvable(message.into())
+    }
+
+    // Moves a parse error of a single line or block to where that starts in the whole input
+    pub fn at_line(self, line: usize) -> Self {
+        match self {
+            AocError::Parse { line: offset, column, message } => AocError::Parse { line: line + offset - 1, column, message },
+            error => error,
+        }
+    }
+
+    // Same for a field that starts at column of its line
+    pub fn at_column(self, column: usize) -> Self {
+        match self {
+            AocError::Parse { line, column: offset, message } => AocError::Parse { line, column: column + offset - 1, message },
+            error => error,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Io(path, error) => write!(f, "Could not read {}: {}", path.display(), error),
+            AocError::Write(path, error) => write!(f, "Could not write {}: {}", path.display(), error),
+            AocError::Encode(path, error) => write!(f, "Could not encode {}: {}", path.display(), error),
+            AocError::Format(path, error) => write!(f, "{} is malformed: {}", path.display(), error),
+            AocError::Download(day, error) => write!(f, "Could not download the input for day {}: {}", day, error),
+            AocError::Missing(day, path) => write!(f, "No input for day {}, expected it at {}", day, path.display()),
+            AocError::Offline(day, path) => write!(f, "Input for day {} is missing, expected it at {} and downloads are disabled in offline mode", day, path.display()),
+            AocError::Profile(name) => write!(f, "{} is not a valid profile name, use letters, digits, - and _", name),
+            AocError::Parse { line, column, message } => write!(f, "Parse error at line {}, column {}: {}", line, column, message),
+            AocError::Unsolvable(reason) => write!(f, "Input has no solution: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors_move_to_their_line() {
+        let error = AocError::parse(4, "expected a number").at_line(7);
+        assert!(matches!(error, AocError::Parse { line: 7, column: 4, .. }));
+        assert_eq!("Parse error at line 8, column 4: expected a number", error.at_line(2).to_string());
+        assert!(matches!(AocError::parse(2, "").at_column(5), AocError::Parse { line: 1, column: 6, .. }));
+
+        assert!(matches!(AocError::unsolvable("no path").at_line(3), AocError::Unsolvable(_)));
+    }
+}
@@ -2,6 +2,7 @@
 #[macro_use]
 extern crate lazy_static;
 
+#[allow(dead_code)]
 mod day_template;
 mod day1;
 mod day2;
@@ -28,35 +29,120 @@ mod day22;
 mod day23;
 mod day24;
 mod day25;
+pub mod cli;
+pub mod error;
+pub mod report;
+pub mod solution;
 pub mod utils;
 
-pub fn get_days() -> Vec<fn()> {
-    vec![
-        day_template::run_day,
-        day1::run_day,
-        day2::run_day,
-        day3::run_day,
-        day4::run_day,
-        day5::run_day,
-        day6::run_day,
-        day7::run_day,
-        day8::run_day,
-        day9::run_day,
-        day10::run_day,
-        day11::run_day,
-        day12::run_day,
-        day13::run_day,
-        day14::run_day,
-        day15::run_day,
-        day16::run_day,
-        day17::run_day,
-        day18::run_day,
-        day19::run_day,
-        day20::run_day,
-        day21::run_day,
-        day22::run_day,
-        day23::run_day,
-        day24::run_day,
-        day25::run_day,
-    ]
+use std::time::Instant;
+
+use error::Result;
+use solution::{Answer, Frames, Images, Part, Runner, Timed};
+use utils::{bench::Timing, input::InputProvider};
+
+pub struct Day {
+    pub number: u32,
+    pub name: &'static str,
+    solution: &'static dyn Runner,
+}
+
+impl Day {
+    const fn new(number: u32, name: &'static str, solution: &'static dyn Runner) -> Self {
+        Day {
+            number,
+            name,
+            solution,
+        }
+    }
+
+    pub fn load_input(&self, provider: &InputProvider) -> Result<String> {
+        provider.load(self.number)
+    }
+
+    pub fn solve_input(&self, input: &str) -> Result<(Answer, Answer)> {
+        self.solution.run(input)
+    }
+
+    pub fn solve_part(&self, input: &str, part: Part) -> Result<Answer> {
+        self.solution.run_part(input, part)
+    }
+
+    // None for days that need the whole input at once. Opening the reader doesn't read files or stdin yet,
+    // so those days can still load the input afterwards.
+    pub fn solve_stream(&self, provider: &InputProvider) -> Result<Option<(Answer, Answer)>> {
+        self.solution.run_stream(provider.reader(self.number)?).transpose()
+    }
+
+    pub fn solve_timed(&self, input: &str) -> Result<Timed> {
+        self.solution.run_timed(input)
+    }
+
+    // None for days that are not simulations
+    pub fn frames(&self, input: &str) -> Result<Option<Frames>> {
+        self.solution.run_frames(input)
+    }
+
+    pub fn images(&self, input: &str) -> Result<Option<Images>> {
+        self.solution.run_images(input)
+    }
+
+    // Loads the input and solves both parts once, timing every phase on its own
+    pub fn time(&self, provider: &InputProvider) -> Result<Timing> {
+        let start = Instant::now();
+        let input = self.load_input(provider)?;
+        let input_time = start.elapsed();
+        let Timed { parse, parts: [(part1, part1_time), (part2, part2_time)] } = self.solution.run_timed(&input)?;
+        part1?;
+        part2?;
+        Ok(Timing { input: input_time, parse, part1: part1_time, part2: part2_time })
+    }
+}
+
+static DAYS: [Day; 25] = [
+    Day::new(day1::DAY, "Sonar Sweep", &day1::Day1),
+    Day::new(day2::DAY, "Dive!", &day2::Day2),
+    Day::new(day3::DAY, "Binary Diagnostic", &day3::Day3),
+    Day::new(day4::DAY, "Giant Squid", &day4::Day4),
+    Day::new(day5::DAY, "Hydrothermal Venture", &day5::Day5),
+    Day::new(day6::DAY, "Lanternfish", &day6::Day6),
+    Day::new(day7::DAY, "The Treachery of Whales", &day7::Day7),
+    Day::new(day8::DAY, "Seven Segment Search", &day8::Day8),
+    Day::new(day9::DAY, "Smoke Basin", &day9::Day9),
+    Day::new(day10::DAY, "Syntax Scoring", &day10::Day10),
+    Day::new(day11::DAY, "Dumbo Octopus", &day11::Day11),
+    Day::new(day12::DAY, "Passage Pathing", &day12::Day12),
+    Day::new(day13::DAY, "Transparent Origami", &day13::Day13),
+    Day::new(day14::DAY, "Extended Polymerization", &day14::Day14),
+    Day::new(day15::DAY, "Chiton", &day15::Day15),
+    Day::new(day16::DAY, "Packet Decoder", &day16::Day16),
+    Day::new(day17::DAY, "Trick Shot", &day17::Day17),
+    Day::new(day18::DAY, "Snailfish", &day18::Day18),
+    Day::new(day19::DAY, "Beacon Scanner", &day19::Day19),
+    Day::new(day20::DAY, "Trench Map", &day20::Day20),
+    Day::new(day21::DAY, "Dirac Dice", &day21::Day21),
+    Day::new(day22::DAY, "Reactor Reboot", &day22::Day22),
+    Day::new(day23::DAY, "Amphipod", &day23::Day23),
+    Day::new(day24::DAY, "Arithmetic Logic Unit", &day24::Day24),
+    Day::new(day25::DAY, "Sea Cucumber", &day25::Day25),
+];
+
+pub fn get_days() -> &'static [Day] {
+    &DAYS
+}
+
+pub fn get_day(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_lists_every_day_in_order() {
+        assert!(get_days().iter().map(|day| day.number).eq(1..=25));
+        assert_eq!("Sonar Sweep", get_day(1).unwrap().name);
+        assert!(get_day(0).is_none());
+    }
 }
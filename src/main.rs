@@ -1,13 +1,5 @@
-use aoc_2021;
-use std::env;
+use std::process::ExitCode;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if 2 == args.len() {
-        aoc_2021::get_days()[args[1].parse::<usize>().unwrap()]();
-    } else {
-        for call in aoc_2021::get_days() {
-            call();
-        }
-    }
+fn main() -> ExitCode {
+    aoc_2021::cli::run()
 }
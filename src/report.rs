@@ -0,0 +1,152 @@
+use std::{io::{self, Write}, time::Duration};
+
+use clap::ValueEnum;
+use serde::{Serialize, Serializer};
+
+use crate::solution::{Answer, Part};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Error,
+}
+
+// One part of one day, the unit every backend reports on
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Record {
+    pub day: u32,
+    #[serde(skip)]
+    pub name: &'static str,
+    pub part: Part,
+    pub answer: Option<String>,
+    #[serde(rename = "seconds", serialize_with = "seconds")]
+    pub duration: Duration,
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Record {
+    pub fn new(day: u32, name: &'static str, part: Part, outcome: &Result<Answer, String>, duration: Duration) -> Self {
+        let (answer, status, error) = match outcome {
+            Ok(answer) => (Some(answer.to_string()), Status::Ok, None),
+            Err(error) => (None, Status::Error, Some(error.clone())),
+        };
+        Record { day, name, part, answer, duration, status, error }
+    }
+}
+
+fn seconds<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64())
+}
+
+pub fn write(format: Format, records: &[Record], out: &mut impl Write) -> io::Result<()> {
+    match format {
+        Format::Text => write_text(records, out),
+        Format::Json => writeln!(out, "{}", serde_json::to_string_pretty(records).unwrap()),
+        Format::Csv => {
+            write_csv_row(out, &["day", "part", "answer", "seconds", "status", "error"])?;
+            for record in records {
+                write_csv_row(out, &[
+                    &record.day.to_string(),
+                    &record.part.to_string(),
+                    record.answer.as_deref().unwrap_or_default(),
+                    &record.duration.as_secs_f64().to_string(),
+                    if record.status == Status::Ok { "ok" } else { "error" },
+                    record.error.as_deref().unwrap_or_default(),
+                ])?;
+            }
+            Ok(())
+        },
+    }
+}
+
+// Days whose parts failed for the same reason, e.g. a missing input, report it once
+fn write_text(records: &[Record], out: &mut impl Write) -> io::Result<()> {
+    for day in records.chunk_by(|a, b| a.day == b.day) {
+        writeln!(out, "Running day {}: {}", day[0].day, day[0].name)?;
+        if let Some(error) = &day[0].error {
+            if day.iter().all(|record| record.error.as_ref() == Some(error)) {
+                writeln!(out, "\tFailed: {}", error)?;
+                continue;
+            }
+        }
+        for record in day {
+            match (&record.answer, &record.error) {
+                (Some(answer), _) => writeln!(out, "\tPart {}: {}", record.part, answer)?,
+                (None, error) => writeln!(out, "\tPart {} failed: {}", record.part, error.as_deref().unwrap_or_default())?,
+            }
+        }
+    }
+    Ok(())
+}
+
+// Fields with separators, quotes or line breaks are quoted as in RFC 4180
+pub fn write_csv_row(out: &mut impl Write, fields: &[&str]) -> io::Result<()> {
+    let fields: Vec<_> = fields.iter()
+        .map(|field| if field.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        })
+        .collect();
+    writeln!(out, "{}", fields.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record::new(1, "Sonar Sweep", Part::One, &Ok(Answer::Number(7)), Duration::from_millis(2)),
+            Record::new(1, "Sonar Sweep", Part::Two, &Ok(Answer::Number(5)), Duration::from_millis(3)),
+            Record::new(13, "Transparent Origami", Part::One, &Ok(Answer::Number(17)), Duration::from_millis(1)),
+            Record::new(13, "Transparent Origami", Part::Two, &Err("no \"fold\", sorry".to_string()), Duration::ZERO),
+            Record::new(19, "Beacon Scanner", Part::One, &Err("No input".to_string()), Duration::ZERO),
+            Record::new(19, "Beacon Scanner", Part::Two, &Err("No input".to_string()), Duration::ZERO),
+        ]
+    }
+
+    fn report(format: Format) -> String {
+        let mut out = Vec::new();
+        write(format, &records(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn text_reports_every_part_the_same_way() {
+        assert_eq!(
+            "Running day 1: Sonar Sweep\n\tPart 1: 7\n\tPart 2: 5\n\
+             Running day 13: Transparent Origami\n\tPart 1: 17\n\tPart 2 failed: no \"fold\", sorry\n\
+             Running day 19: Beacon Scanner\n\tFailed: No input\n",
+            report(Format::Text),
+        );
+    }
+
+    #[test]
+    fn json_and_csv_hold_one_record_per_part() {
+        let json: serde_json::Value = serde_json::from_str(&report(Format::Json)).unwrap();
+        assert_eq!(6, json.as_array().unwrap().len());
+        assert_eq!(serde_json::json!({
+            "day": 1, "part": 2, "answer": "5", "seconds": 0.003, "status": "ok",
+        }), json[1]);
+        assert_eq!("error", json[3]["status"]);
+        assert!(json[3]["answer"].is_null());
+
+        let csv = report(Format::Csv);
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!("day,part,answer,seconds,status,error", lines[0]);
+        assert_eq!("1,1,7,0.002,ok,", lines[1]);
+        assert_eq!("13,2,,0,error,\"no \"\"fold\"\", sorry\"", lines[4]);
+        assert_eq!(7, lines.len());
+    }
+}
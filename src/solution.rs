@@ -0,0 +1,252 @@
+use std::{fmt, time::{Duration, Instant}};
+
+use serde::{Serialize, Serializer};
+
+use crate::error::Result;
+use crate::utils::{image::Image, input::InputReader};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+macro_rules! answer_from_number {
+    ($($number:ty),*) => {
+        $(
+            impl From<$number> for Answer {
+                fn from(number: $number) -> Self {
+                    Answer::Number(number as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_number!(u32, u64, usize, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl Serialize for Part {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_u8(if *self == Part::One { 1 } else { 2 })
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = String;
+
+    fn try_from(part: u8) -> std::result::Result<Self, Self::Error> {
+        match part {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(format!("There is no part {}, only 1 and 2", part)),
+        }
+    }
+}
+
+pub trait Solution {
+    type Input;
+
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer>;
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer>;
+
+    fn solve(&self, input: &str) -> Result<(Answer, Answer)> {
+        let input = self.parse(input)?;
+        Ok((self.part1(&input)?, self.part2(&input)?))
+    }
+
+    fn solve_part(&self, input: &str, part: Part) -> Result<Answer> {
+        let input = self.parse(input)?;
+        match part {
+            Part::One => self.part1(&input),
+            Part::Two => self.part2(&input),
+        }
+    }
+
+    // Days that can solve both parts in one pass over the lines, without holding the input in memory
+    fn stream(&self, _input: InputReader) -> Option<Result<(Answer, Answer)>> {
+        None
+    }
+
+    // Days that simulate step by step can show every state they pass through
+    fn frames(&self, _input: Self::Input) -> Option<Frames> {
+        None
+    }
+
+    // The same for days worth a picture, one pixel per cell
+    fn images(&self, _input: Self::Input) -> Option<Images> {
+        None
+    }
+}
+
+// Rendered states of a simulation, computed as they are asked for
+pub type Frames = Box<dyn Iterator<Item = String>>;
+pub type Images = Box<dyn Iterator<Item = Image>>;
+
+// Answers of both parts, with how long parsing and each of the parts took
+#[derive(Debug)]
+pub struct Timed {
+    pub parse: Duration,
+    pub parts: [(Result<Answer>, Duration); 2],
+}
+
+// Object safe view on a Solution, so days with different input types can share one registry
+pub trait Runner: Sync {
+    fn run(&self, input: &str) -> Result<(Answer, Answer)>;
+
+    fn run_part(&self, input: &str, part: Part) -> Result<Answer>;
+
+    // Only fails if the input can't be parsed, the parts succeed or fail on their own
+    fn run_timed(&self, input: &str) -> Result<Timed>;
+
+    fn run_frames(&self, input: &str) -> Result<Option<Frames>>;
+
+    fn run_stream(&self, input: InputReader) -> Option<Result<(Answer, Answer)>>;
+
+    fn run_images(&self, input: &str) -> Result<Option<Images>>;
+}
+
+impl<S: Solution + Sync> Runner for S {
+    fn run(&self, input: &str) -> Result<(Answer, Answer)> {
+        self.solve(input)
+    }
+
+    fn run_part(&self, input: &str, part: Part) -> Result<Answer> {
+        self.solve_part(input, part)
+    }
+
+    fn run_timed(&self, input: &str) -> Result<Timed> {
+        let start = Instant::now();
+        let input = self.parse(input)?;
+        let parse = start.elapsed();
+        let parts = [Part::One, Part::Two].map(|part| {
+            let start = Instant::now();
+            let answer = match part {
+                Part::One => self.part1(&input),
+                Part::Two => self.part2(&input),
+            };
+            (answer, start.elapsed())
+        });
+        Ok(Timed { parse, parts })
+    }
+
+    fn run_frames(&self, input: &str) -> Result<Option<Frames>> {
+        Ok(self.frames(self.parse(input)?))
+    }
+
+    fn run_images(&self, input: &str) -> Result<Option<Images>> {
+        Ok(self.images(self.parse(input)?))
+    }
+
+    fn run_stream(&self, input: InputReader) -> Option<Result<(Answer, Answer)>> {
+        self.stream(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::AocError, utils::parse};
+
+    struct Echo;
+
+    impl Solution for Echo {
+        type Input = Vec<u32>;
+
+        fn parse(&self, input: &str) -> Result<Self::Input> {
+            parse::lines(input, |line| parse::number(line, 1))
+        }
+
+        fn part1(&self, input: &Self::Input) -> Result<Answer> {
+            Ok(input.iter().sum::<u32>().into())
+        }
+
+        fn part2(&self, input: &Self::Input) -> Result<Answer> {
+            Ok(format!("{} lines", input.len()).into())
+        }
+    }
+
+    #[test]
+    fn runner_parses_and_solves_both_parts() {
+        let runner: &dyn Runner = &Echo;
+        let (part1, part2) = runner.run("1\n2\n3\n").unwrap();
+        assert_eq!(Answer::Number(6), part1);
+        assert_eq!(Answer::Text("3 lines".to_string()), part2);
+    }
+
+    #[test]
+    fn runner_solves_single_part() {
+        let runner: &dyn Runner = &Echo;
+        assert_eq!(Answer::Number(3), runner.run_part("1\n2\n", Part::One).unwrap());
+        assert_eq!(Answer::Text("2 lines".to_string()), runner.run_part("1\n2\n", Part::Two).unwrap());
+    }
+
+    #[test]
+    fn runner_reports_malformed_lines() {
+        let runner: &dyn Runner = &Echo;
+        let error = runner.run("1\n2\nthree\n").unwrap_err();
+        assert!(matches!(error, AocError::Parse { line: 3, column: 1, .. }), "{}", error);
+        assert!(runner.run_timed("1\nthree\n").is_err());
+    }
+
+    #[test]
+    fn runner_times_both_parts() {
+        let runner: &dyn Runner = &Echo;
+        let timed = runner.run_timed("1\n2\n").unwrap();
+        assert_eq!(Answer::Number(3), *timed.parts[0].0.as_ref().unwrap());
+        assert_eq!(Answer::Text("2 lines".to_string()), *timed.parts[1].0.as_ref().unwrap());
+    }
+
+    #[test]
+    fn solutions_without_hooks_have_no_frames_images_or_stream() {
+        let runner: &dyn Runner = &Echo;
+        assert!(runner.run_frames("1\n2\n").unwrap().is_none());
+        assert!(runner.run_images("1\n2\n").unwrap().is_none());
+        assert!(runner.run_stream(InputReader::from_string("1\n2\n".to_string())).is_none());
+    }
+
+    #[test]
+    fn answers_display_like_their_values() {
+        assert_eq!("-42", Answer::from(-42_i64).to_string());
+        assert_eq!("18446744073709551615", Answer::from(u64::MAX).to_string());
+        assert_eq!("ABCD", Answer::from("ABCD").to_string());
+    }
+}
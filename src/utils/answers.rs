@@ -0,0 +1,209 @@
+use std::{collections::BTreeMap, fmt, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AocError, Result};
+use crate::solution::{Answer, Part};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayAnswers {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+// The answers a profile expects, stored as {"5": {"part1": "5145", "part2": "16518"}}
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ExpectedAnswers(BTreeMap<u32, DayAnswers>);
+
+impl ExpectedAnswers {
+    // A profile without an answers file simply expects nothing
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|error| AocError::Format(path.to_path_buf(), error.to_string())),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(ExpectedAnswers::default()),
+            Err(error) => Err(AocError::Io(path.to_path_buf(), error)),
+        }
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&str> {
+        let answers = self.0.get(&day)?;
+        match part {
+            Part::One => answers.part1.as_deref(),
+            Part::Two => answers.part2.as_deref(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // Answers of other take precedence over the ones already known
+    pub fn merge(&mut self, other: ExpectedAnswers) {
+        for (day, answers) in other.0 {
+            let known = self.0.entry(day).or_default();
+            if answers.part1.is_some() {
+                known.part1 = answers.part1;
+            }
+            if answers.part2.is_some() {
+                known.part2 = answers.part2;
+            }
+        }
+    }
+}
+
+pub const DEFAULT_PROFILE: &str = "default";
+
+// All known answers, stored as {"<profile>": {"<day>": {"part1": "...", "part2": "..."}}}
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Manifest(BTreeMap<String, ExpectedAnswers>);
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|error| AocError::Format(path.to_path_buf(), error.to_string())),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Manifest::default()),
+            Err(error) => Err(AocError::Io(path.to_path_buf(), error)),
+        }
+    }
+
+    pub fn profile(&self, profile: &str) -> ExpectedAnswers {
+        self.0.get(profile).cloned().unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing { actual: String },
+    Error(String),
+}
+
+impl Verdict {
+    pub fn judge(expected: Option<&str>, actual: &std::result::Result<Answer, String>) -> Self {
+        match (expected, actual) {
+            (_, Err(error)) => Verdict::Error(error.clone()),
+            (None, Ok(actual)) => Verdict::Missing { actual: actual.to_string() },
+            (Some(expected), Ok(actual)) if expected == actual.to_string() => Verdict::Pass,
+            (Some(expected), Ok(actual)) => Verdict::Fail {
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            },
+        }
+    }
+
+    pub fn status(&self) -> &'static str {
+        match self {
+            Verdict::Pass => "pass",
+            Verdict::Fail { .. } => "fail",
+            Verdict::Missing { .. } => "missing",
+            Verdict::Error(_) => "error",
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Fail { .. } | Verdict::Error(_))
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected, actual } => write!(f, "FAIL expected {}, got {}", expected, actual),
+            Verdict::Missing { actual } => write!(f, "missing (got {})", actual),
+            Verdict::Error(error) => write!(f, "ERROR {}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_days;
+    use crate::utils::input::get_input;
+
+    #[test]
+    fn answers_are_keyed_by_day_and_part() {
+        let answers: ExpectedAnswers = serde_json::from_str(r#"{
+            "1": { "part1": "7", "part2": "5" },
+            "13": { "part1": "17" }
+        }"#).unwrap();
+
+        assert_eq!(Some("7"), answers.get(1, Part::One));
+        assert_eq!(Some("17"), answers.get(13, Part::One));
+        assert_eq!(None, answers.get(13, Part::Two));
+        assert_eq!(None, answers.get(2, Part::One));
+    }
+
+    #[test]
+    fn missing_file_expects_nothing() {
+        let answers = ExpectedAnswers::load(Path::new("does/not/exist/answers.json")).unwrap();
+        assert!(answers.is_empty());
+    }
+
+    #[test]
+    fn manifest_is_keyed_by_profile() {
+        let manifest: Manifest = serde_json::from_str(r#"{
+            "default": { "1": { "part1": "1215", "part2": "1150" } },
+            "second": { "1": { "part1": "1400" } }
+        }"#).unwrap();
+        assert_eq!(Some("1215"), manifest.profile(DEFAULT_PROFILE).get(1, Part::One));
+        assert_eq!(Some("1400"), manifest.profile("second").get(1, Part::One));
+        assert_eq!(None, manifest.profile("second").get(1, Part::Two));
+        assert!(manifest.profile("third").is_empty());
+
+        let mut second = manifest.profile("second");
+        second.merge(serde_json::from_str(r#"{ "1": { "part2": "1420" } }"#).unwrap());
+        assert_eq!(Some("1400"), second.get(1, Part::One));
+        assert_eq!(Some("1420"), second.get(1, Part::Two));
+    }
+
+    #[test]
+    fn verdicts_compare_expected_and_actual() {
+        assert_eq!(Verdict::Pass, Verdict::judge(Some("7"), &Ok(Answer::Number(7))));
+        assert_eq!(
+            Verdict::Fail { expected: "7".to_string(), actual: "8".to_string() },
+            Verdict::judge(Some("7"), &Ok(Answer::Number(8))),
+        );
+        assert_eq!(
+            Verdict::Missing { actual: "8".to_string() },
+            Verdict::judge(None, &Ok(Answer::Number(8))),
+        );
+        assert!(Verdict::judge(Some("7"), &Err("No input".to_string())).is_failure());
+        assert!(!Verdict::judge(None, &Ok(Answer::Number(8))).is_failure());
+    }
+
+    #[test]
+    fn answers_round_trip() {
+        let json = r#"{"6":{"part2":"26984457539"}}"#;
+        let answers: ExpectedAnswers = serde_json::from_str(json).unwrap();
+        assert_eq!(json, serde_json::to_string(&answers).unwrap());
+    }
+
+    // Days without a downloaded input are skipped, see get_input
+    #[test]
+    fn solutions_give_the_answers_of_the_manifest() {
+        let manifest = Manifest::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.json")).unwrap();
+        let expected = manifest.profile(DEFAULT_PROFILE);
+        let mut failures = Vec::new();
+        for day in get_days() {
+            let Some(input) = get_input(day.number) else { continue };
+            let answers = day.solve_input(&input).map_err(|error| error.to_string());
+            for part in [Part::One, Part::Two] {
+                let actual = answers.clone().map(|(part1, part2)| if part == Part::One { part1 } else { part2 });
+                let verdict = Verdict::judge(expected.get(day.number, part), &actual);
+                if verdict.is_failure() {
+                    failures.push(format!("day {} part {}: {}", day.number, part, verdict));
+                }
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+}
@@ -0,0 +1,188 @@
+use std::{collections::BTreeMap, fs, io, path::Path, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AocError, Result};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase {
+    Input,
+    Parse,
+    Part1,
+    Part2,
+}
+
+pub const PHASES: [Phase; 4] = [Phase::Input, Phase::Parse, Phase::Part1, Phase::Part2];
+
+impl Phase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Input => "input",
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        }
+    }
+}
+
+// How long each phase of one run took
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Timing {
+    pub input: Duration,
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timing {
+    pub fn get(&self, phase: Phase) -> Duration {
+        match phase {
+            Phase::Input => self.input,
+            Phase::Parse => self.parse,
+            Phase::Part1 => self.part1,
+            Phase::Part2 => self.part2,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn of(samples: &[Duration]) -> Option<Self> {
+        let mut samples = samples.to_vec();
+        samples.sort_unstable();
+        let middle = samples.len() / 2;
+        let median = match samples.len() {
+            0 => return None,
+            len if len % 2 == 0 => (samples[middle - 1] + samples[middle]) / 2,
+            _ => samples[middle],
+        };
+        Some(Stats { min: samples[0], median, max: samples[samples.len() - 1] })
+    }
+}
+
+// Per phase statistics over all iterations of a day
+pub fn summarize(timings: &[Timing]) -> Option<BTreeMap<Phase, Stats>> {
+    PHASES.iter()
+        .map(|&phase| {
+            let samples: Vec<_> = timings.iter().map(|timing| timing.get(phase)).collect();
+            Stats::of(&samples).map(|stats| (phase, stats))
+        })
+        .collect()
+}
+
+// Median seconds of earlier runs, stored as {"19": {"parse": 0.0004, "part1": 1.2, ...}}
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Baseline(BTreeMap<u32, BTreeMap<String, f64>>);
+
+impl Baseline {
+    // Without a baseline file there is nothing to regress from
+    pub fn load(path: &Path) -> Result<Self> {
+        let baseline: Baseline = match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|error| AocError::Format(path.to_path_buf(), error.to_string()))?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Baseline::default()),
+            Err(error) => return Err(AocError::Io(path.to_path_buf(), error)),
+        };
+        baseline.validate().map_err(|error| AocError::Format(path.to_path_buf(), error))?;
+        Ok(baseline)
+    }
+
+    // Every entry has to be a duration, so that get never has to deal with one that isn't
+    fn validate(&self) -> std::result::Result<(), String> {
+        for (day, medians) in &self.0 {
+            for (phase, &seconds) in medians {
+                Duration::try_from_secs_f64(seconds)
+                    .map_err(|error| format!("{} of day {} is {} seconds: {}", phase, day, seconds, error))?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).unwrap();
+        fs::write(path, json + "\n").map_err(|error| AocError::Write(path.to_path_buf(), error))
+    }
+
+    pub fn get(&self, day: u32, phase: Phase) -> Option<Duration> {
+        self.0.get(&day)?
+            .get(phase.name())
+            .and_then(|&seconds| Duration::try_from_secs_f64(seconds).ok())
+    }
+
+    // Replaces everything known about day
+    pub fn insert(&mut self, day: u32, stats: &BTreeMap<Phase, Stats>) {
+        let medians = stats.iter()
+            .map(|(phase, stats)| (phase.name().to_string(), stats.median.as_secs_f64()))
+            .collect();
+        self.0.insert(day, medians);
+    }
+}
+
+// Below this any difference is noise, no matter how large it is relative to the baseline
+const NOISE: Duration = Duration::from_micros(100);
+
+// A phase regressed if its median is more than threshold percent slower than the baseline
+pub fn is_regression(baseline: Duration, median: Duration, threshold: f64) -> bool {
+    median > baseline + NOISE && median.as_secs_f64() > baseline.as_secs_f64() * (1.0 + threshold / 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn stats_take_min_median_and_max() {
+        assert_eq!(Some(Stats { min: ms(1), median: ms(3), max: ms(9) }), Stats::of(&[ms(9), ms(1), ms(3)]));
+        assert_eq!(Some(Stats { min: ms(1), median: ms(4), max: ms(9) }), Stats::of(&[ms(9), ms(5), ms(1), ms(3)]));
+        assert_eq!(None, Stats::of(&[]));
+    }
+
+    #[test]
+    fn baseline_stores_medians_per_phase() {
+        let timings = [
+            Timing { input: ms(1), parse: ms(2), part1: ms(30), part2: ms(40) },
+            Timing { input: ms(3), parse: ms(2), part1: ms(10), part2: ms(60) },
+            Timing { input: ms(2), parse: ms(2), part1: ms(20), part2: ms(50) },
+        ];
+        let mut baseline = Baseline::default();
+        baseline.insert(19, &summarize(&timings).unwrap());
+
+        let json = serde_json::to_string(&baseline).unwrap();
+        assert_eq!(r#"{"19":{"input":0.002,"parse":0.002,"part1":0.02,"part2":0.05}}"#, json);
+        let baseline: Baseline = serde_json::from_str(&json).unwrap();
+        assert_eq!(Some(ms(20)), baseline.get(19, Phase::Part1));
+        assert_eq!(None, baseline.get(22, Phase::Part1));
+    }
+
+    #[test]
+    fn baseline_rejects_entries_that_are_no_duration() {
+        let path = std::env::temp_dir().join(format!("aoc-2021-baseline-{}.json", std::process::id()));
+        for seconds in ["-1", "1e300"] {
+            fs::write(&path, format!(r#"{{"1":{{"part1":{}}}}}"#, seconds)).unwrap();
+            let error = Baseline::load(&path).unwrap_err();
+            assert!(matches!(error, AocError::Format(_, _)), "{}", error);
+        }
+        fs::write(&path, r#"{"1":{"part1":0.5}}"#).unwrap();
+        assert_eq!(Some(ms(500)), Baseline::load(&path).unwrap().get(1, Phase::Part1));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn regressions_exceed_threshold_and_noise() {
+        assert!(is_regression(ms(100), ms(111), 10.0));
+        assert!(!is_regression(ms(100), ms(109), 10.0));
+        assert!(!is_regression(ms(100), ms(50), 10.0));
+        assert!(!is_regression(Duration::from_micros(10), Duration::from_micros(50), 10.0));
+    }
+}
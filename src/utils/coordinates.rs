@@ -1,199 +1,322 @@
-use num;
+use std::{array, ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign}};
 
-#[derive(Clone, Debug, Hash)]
-pub struct Vector<T> {
-    dimensions: Vec<T>
+use num::{Signed, Zero};
+
+// Lives on the stack, so arithmetic on points never allocates
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector<T, const N: usize>([T; N]);
+
+impl<T, const N: usize> Vector<T, N> {
+    pub const fn new(coordinates: [T; N]) -> Self {
+        Vector(coordinates)
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Vector<U, N> {
+        Vector(self.0.map(f))
+    }
+
+    pub fn coordinates(&self) -> &[T; N] {
+        &self.0
+    }
 }
 
-impl Vector<isize> {
-    pub fn normalized(&self) -> Self {
-        Vector {
-            dimensions: self.dimensions.iter()
-                .map(|&dimension| num::signum(dimension))
-                .collect(),
-        }
+impl<T: Copy, const N: usize> Vector<T, N> {
+    // Combines the coordinates pairwise, e.g. with Ord::min for the corner of a bounding box
+    pub fn zip_with<U: Copy, V>(self, other: Vector<U, N>, mut f: impl FnMut(T, U) -> V) -> Vector<V, N> {
+        Vector(array::from_fn(|idx| f(self.0[idx], other.0[idx])))
     }
+}
 
-    pub fn rotate(&mut self, axis: char, degree: u32) {
-        let degree = (degree as f64).to_radians();
-        match axis {
-            'x' => self.rotate_x(degree),
-            'y' => self.rotate_y(degree),
-            'z' => self.rotate_z(degree),
-            _ => unimplemented!(),
-        }
+impl<T: Copy + Zero + Mul<Output = T>, const N: usize> Vector<T, N> {
+    pub fn zero() -> Self {
+        Vector([T::zero(); N])
     }
 
-    fn rotate_x(&mut self, degree: f64) {
-        let new_y = degree.cos() * (self.dimensions[1] as f64) - degree.sin() * (self.dimensions[2] as f64);
-        let new_z = degree.sin() * (self.dimensions[1] as f64) + degree.cos() * (self.dimensions[2] as f64);
-        self.dimensions[1] = new_y.round() as isize;
-        self.dimensions[2] = new_z.round() as isize;
+    pub fn dot(self, other: Self) -> T {
+        self.0.iter()
+            .zip(other.0)
+            .fold(T::zero(), |sum, (&l, r)| sum + l * r)
+    }
+}
+
+impl<T: Copy + Signed + Ord, const N: usize> Vector<T, N> {
+    // Every coordinate reduced to -1, 0 or 1, a single step along a horizontal, vertical or diagonal line
+    pub fn signum(self) -> Self {
+        self.map(|value| value.signum())
+    }
+
+    pub fn manhattan(self) -> T {
+        self.0.iter().fold(T::zero(), |sum, value| sum + value.abs())
     }
 
-    fn rotate_y(&mut self, degree: f64) {
-        let new_x = degree.cos() * (self.dimensions[0] as f64) + degree.sin() * (self.dimensions[2] as f64);
-        let new_z = degree.cos() * (self.dimensions[2] as f64) - degree.sin() * (self.dimensions[0] as f64);
-        self.dimensions[0] = new_x.round() as isize;
-        self.dimensions[2] = new_z.round() as isize;
+    pub fn chebyshev(self) -> T {
+        self.0.iter().fold(T::zero(), |max, value| max.max(value.abs()))
     }
 
-    fn rotate_z(&mut self, degree: f64) {
-        let new_x = degree.cos() * (self.dimensions[0] as f64) - degree.sin() * (self.dimensions[1] as f64);
-        let new_y = degree.sin() * (self.dimensions[0] as f64) + degree.cos() * (self.dimensions[1] as f64);
-        self.dimensions[0] = new_x.round() as isize;
-        self.dimensions[1] = new_y.round() as isize;
+    pub fn manhattan_distance(self, other: Self) -> T {
+        (self - other).manhattan()
     }
 
-    pub fn manhattan_distance(&self) -> u64 {
-        self.dimensions.iter()
-            .fold(0, |distance, value| (distance + value.abs())) as u64
+    pub fn chebyshev_distance(self, other: Self) -> T {
+        (self - other).chebyshev()
     }
 }
 
-impl Vector<usize> {
-    pub fn normalized(&self) -> Self {
-        Vector {
-            dimensions: self.dimensions.iter()
-                .map(|&dimension| if dimension > num::zero() { 1 } else { 0 })
-                .collect(),
-        }
+impl<T: Copy> Vector<T, 2> {
+    pub fn x(&self) -> T {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> T {
+        self.0[1]
     }
 }
 
-impl<T> From<(T, T)> for Vector<T> {
-    fn from(input: (T, T)) -> Self {
-        Vector {
-            dimensions: vec![input.0, input.1],
-        }
+impl<T: Copy> Vector<T, 3> {
+    pub fn x(&self) -> T {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> T {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> T {
+        self.0[2]
     }
 }
 
-impl<T> From<(T, T, T)> for Vector<T> {
-    fn from(input: (T, T, T)) -> Self {
-        Vector {
-            dimensions: vec![input.0, input.1, input.2],
-        }
+impl<T: Copy + Mul<Output = T> + Sub<Output = T>> Vector<T, 3> {
+    pub fn cross(self, other: Self) -> Self {
+        let [a1, a2, a3] = self.0;
+        let [b1, b2, b3] = other.0;
+        Vector([a2 * b3 - a3 * b2, a3 * b1 - a1 * b3, a1 * b2 - a2 * b1])
     }
 }
 
-impl<T> From<Vec<T>> for Vector<T> {
-    fn from(input: Vec<T>) -> Self {
-        Vector {
-            dimensions: input,
-        }
+impl<T> From<(T, T)> for Vector<T, 2> {
+    fn from((x, y): (T, T)) -> Self {
+        Vector([x, y])
+    }
+}
+
+impl<T> From<(T, T, T)> for Vector<T, 3> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Vector([x, y, z])
     }
 }
 
-impl<T: Copy + std::ops::Add<Output = T>> std::ops::Add for &Vector<T> {
-    type Output = Vector<T>;
+impl<T, const N: usize> From<[T; N]> for Vector<T, N> {
+    fn from(coordinates: [T; N]) -> Self {
+        Vector(coordinates)
+    }
+}
+
+impl<T: Copy + Add<Output = T>, const N: usize> Add for Vector<T, N> {
+    type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Vector {
-            dimensions: self.dimensions.iter()
-                .zip(rhs.dimensions.iter())
-                .map(|(&l, &r)| l + r)
-                .collect(),
-        }
+        self.zip_with(rhs, |l, r| l + r)
     }
 }
 
-impl std::ops::Add<&Vector<isize>> for &Vector<usize> {
-    type Output = Vector<usize>;
+impl<T: Copy + Sub<Output = T>, const N: usize> Sub for Vector<T, N> {
+    type Output = Self;
 
-    fn add(self, rhs: &Vector<isize>) -> Self::Output {
-        Vector {
-            dimensions: self.dimensions.iter()
-                .zip(rhs.dimensions.iter())
-                .map(|(&l, &r)| (l as isize + r) as usize)
-                .collect(),
-        }
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |l, r| l - r)
     }
 }
 
-impl std::ops::Add<&Vector<usize>> for &Vector<isize> {
-    type Output = Vector<isize>;
+impl<T: Copy + Add<Output = T>, const N: usize> AddAssign for Vector<T, N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
 
-    fn add(self, rhs: &Vector<usize>) -> Self::Output {
-        Vector {
-            dimensions: self.dimensions.iter()
-                .zip(rhs.dimensions.iter())
-                .map(|(&l, &r)| l + r as isize)
-                .collect(),
-        }
+impl<T: Copy + Sub<Output = T>, const N: usize> SubAssign for Vector<T, N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
     }
 }
 
-impl<T: Copy + std::ops::Sub<Output = T> + num::ToPrimitive> std::ops::Sub for Vector<T> {
-    type Output = Vector<isize>;
+impl<T: Neg<Output = T>, const N: usize> Neg for Vector<T, N> {
+    type Output = Self;
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        Vector {
-            dimensions: self.dimensions.iter()
-                .zip(rhs.dimensions.iter())
-                .map(|(&l, &r)| -(l.to_isize().unwrap() -  r.to_isize().unwrap()))
-                .collect::<Vec<isize>>(),
-        }
+    fn neg(self) -> Self::Output {
+        self.map(|value| -value)
     }
 }
 
-impl<T: Copy + std::ops::Sub<Output = T> + num::ToPrimitive> std::ops::Sub for &Vector<T> {
-    type Output = Vector<isize>;
+impl<T: Copy + Mul<Output = T>, const N: usize> Mul<T> for Vector<T, N> {
+    type Output = Self;
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        Vector {
-            dimensions: self.dimensions.iter()
-                .zip(rhs.dimensions.iter())
-                .map(|(&l, &r)| -(l.to_isize().unwrap() -  r.to_isize().unwrap()))
-                .collect::<Vec<isize>>(),
-        }
+    fn mul(self, rhs: T) -> Self::Output {
+        self.map(|value| value * rhs)
     }
 }
 
-impl<T> std::ops::Index<usize> for Vector<T> {
+impl<T, const N: usize> Index<usize> for Vector<T, N> {
     type Output = T;
 
     fn index(&self, idx: usize) -> &Self::Output {
-        &self.dimensions[idx]
+        &self.0[idx]
     }
 }
 
-impl<T> std::ops::IndexMut<usize> for Vector<T> {
+impl<T, const N: usize> IndexMut<usize> for Vector<T, N> {
     fn index_mut(&mut self, idx: usize) -> &mut Self::Output {
-        &mut self.dimensions[idx]
+        &mut self.0[idx]
     }
 }
 
-impl<T> std::ops::Index<char> for Vector<T> {
-    type Output = T;
+// A proper rotation of 3D space that maps axes onto axes, so every entry is -1, 0 or 1
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rotation([[i8; 3]; 3]);
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+
+    // Every way to turn a cube, the identity first
+    pub fn all() -> [Rotation; 24] {
+        let mut rotations = [Rotation::IDENTITY; 24];
+        let mut count = 0;
+        for axes in [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]] {
+            for signs in 0..8 {
+                let mut matrix = [[0; 3]; 3];
+                for (row, &axis) in axes.iter().enumerate() {
+                    matrix[row][axis] = if signs & (1 << row) == 0 { 1 } else { -1 };
+                }
+                // The other half mirror space
+                if Rotation(matrix).determinant() == 1 {
+                    rotations[count] = Rotation(matrix);
+                    count += 1;
+                }
+            }
+        }
+        rotations
+    }
+
+    pub fn matrix(&self) -> &[[i8; 3]; 3] {
+        &self.0
+    }
+
+    // Undoes self, which for rotations is the transpose
+    pub fn inverse(self) -> Self {
+        Rotation(array::from_fn(|row| array::from_fn(|column| self.0[column][row])))
+    }
+
+    // Rotates by other first, then by self
+    pub fn compose(self, other: Self) -> Self {
+        Rotation(array::from_fn(|row| array::from_fn(|column| (0..3).map(|k| self.0[row][k] * other.0[k][column]).sum())))
+    }
+
+    pub fn apply<T: Copy + Signed>(self, vector: Vector<T, 3>) -> Vector<T, 3> {
+        Vector(self.0.map(|row| row.iter()
+            .zip(vector.0)
+            .fold(T::zero(), |sum, (&factor, value)| match factor {
+                1 => sum + value,
+                -1 => sum - value,
+                _ => sum,
+            })))
+    }
+
+    pub fn apply_all<T: Copy + Signed>(self, points: &[Vector<T, 3>]) -> Vec<Vector<T, 3>> {
+        points.iter().map(|&point| self.apply(point)).collect()
+    }
 
-    fn index(&self, idx: char) -> &Self::Output {
-        let idx = match idx {
-            'x' => 0,
-            'y' => 1,
-            'z' => 2,
-            _ => panic!("Character indexing is only supported for x, y and z"),
-        };
-        &self.dimensions[idx]
+    fn determinant(&self) -> i8 {
+        let m = self.0;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
     }
 }
 
-impl<T> std::ops::IndexMut<char> for Vector<T> {
-    fn index_mut(&mut self, idx: char) -> &mut Self::Output {
-        let idx = match idx {
-            'x' => 0,
-            'y' => 1,
-            'z' => 2,
-            _ => panic!("Character indexing is only supported for x, y and z"),
-        };
-        &mut self.dimensions[idx]
+impl Mul for Rotation {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.compose(rhs)
     }
 }
 
-impl<T: PartialEq + Copy> std::cmp::PartialEq for Vector<T> {
-    fn eq(&self, other: &Self) -> bool {
-        self.dimensions.iter().zip(other.dimensions.iter()).all(|(&l, &r)| l == r)
+impl<T: Copy + Signed> Mul<Vector<T, 3>> for Rotation {
+    type Output = Vector<T, 3>;
+
+    fn mul(self, rhs: Vector<T, 3>) -> Self::Output {
+        self.apply(rhs)
     }
 }
 
-impl<T: Eq + Copy> std::cmp::Eq for Vector<T> {}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_works_per_coordinate() {
+        let a = Vector::from((1, -2, 3));
+        let b = Vector::from((4, 5, -6));
+        assert_eq!(Vector::new([5, 3, -3]), a + b);
+        assert_eq!(Vector::new([-3, -7, 9]), a - b);
+        assert_eq!(Vector::new([-1, 2, -3]), -a);
+        assert_eq!(Vector::new([2, -4, 6]), a * 2);
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(b, c);
+    }
+
+    #[test]
+    fn products_and_distances() {
+        let x = Vector::from((1, 0, 0));
+        let y = Vector::from((0, 1, 0));
+        assert_eq!(0, x.dot(y));
+        assert_eq!(Vector::from((0, 0, 1)), x.cross(y));
+        assert_eq!(32, Vector::from((1, 2, 3)).dot(Vector::from((4, 5, 6))));
+
+        let a = Vector::from((1, -2));
+        let b = Vector::from((-3, 5));
+        assert_eq!(11, a.manhattan_distance(b));
+        assert_eq!(7, a.chebyshev_distance(b));
+        assert_eq!(Vector::from((-1, 1)), (b - a).signum());
+    }
+
+    #[test]
+    fn vectors_order_and_hash_like_their_coordinates() {
+        let mut points = vec![Vector::from((2, 1)), Vector::from((1, 5)), Vector::from((1, 2))];
+        points.sort();
+        assert_eq!(vec![Vector::from((1, 2)), Vector::from((1, 5)), Vector::from((2, 1))], points);
+        assert_eq!(3, points.iter().collect::<std::collections::HashSet<_>>().len());
+    }
+
+    #[test]
+    fn rotations_form_a_group() {
+        let rotations = Rotation::all();
+        assert_eq!(Rotation::IDENTITY, rotations[0]);
+        assert_eq!(24, rotations.iter().collect::<std::collections::HashSet<_>>().len());
+        for &a in &rotations {
+            assert_eq!(Rotation::IDENTITY, a * a.inverse());
+            for &b in &rotations {
+                assert!(rotations.contains(&(a * b)));
+                assert_eq!(a.apply(b.apply(Vector::from((1, 2, 3)))), (a * b) * Vector::from((1, 2, 3)));
+            }
+        }
+    }
+
+    #[test]
+    fn rotations_turn_points_without_rounding() {
+        let point = Vector::from((1, 2, 3));
+        let images: std::collections::HashSet<_> = Rotation::all().iter().map(|&rotation| rotation * point).collect();
+        assert_eq!(24, images.len());
+        assert!(images.iter().all(|image| image.manhattan() == 6 && image.dot(*image) == 14));
+
+        // A quarter turn around z takes x to y
+        let quarter = Rotation::all().into_iter()
+            .find(|&rotation| rotation * Vector::from((1, 0, 0)) == Vector::from((0, 1, 0)) && rotation * Vector::from((0, 0, 1)) == Vector::from((0, 0, 1)))
+            .unwrap();
+        assert_eq!(vec![Vector::from((0, 1, 0)), Vector::from((-2, 5, 7))], quarter.apply_all(&[Vector::from((1, 0, 0)), Vector::from((5, 2, 7))]));
+        assert_eq!(Rotation::IDENTITY, quarter * quarter * quarter * quarter);
+    }
+}
@@ -0,0 +1,127 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::get_day;
+use crate::utils::gen::Rng;
+
+// A day with a hand-written parser. Mutated inputs may be rejected, but solving them must never panic.
+pub struct Target {
+    pub day: u32,
+    // What the mutations insert, mostly characters the parser cares about
+    alphabet: &'static str,
+    // Valid inputs to start from, taken from the puzzle examples
+    pub corpus: &'static [&'static str],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Crash {
+    pub input: String,
+    pub message: String,
+}
+
+static TARGETS: [Target; 3] = [
+    Target {
+        day: 10,
+        alphabet: "()[]{}<>",
+        corpus: &[
+            "[({(<(())[]>[[{[]{<()<>>\n[(()[<>])]({[<{<<[]>>(\n{([(<{}[<>[]}>{[]{[(<()>\n(((({<>}<{<{<>}{[]{[]{}\n\
+             [[<[([]))<([[{}[[()]]]\n[{[{({}]{}}([{[{{{}}([]\n{<[[]]>}<{[{[{[]{()[[[]\n[<(<(<(<{}))><([]([]()\n\
+             <{([([[(<>()){}]>(<<{{\n<{([{{}}[<[[[<>{}]]]>[]]\n",
+            "[({(<(())[]>[[{[]{<()<>>\n",
+            "{([(<{}[<>[]}>{[]{[(<()>\n[(()[<>])]({[<{<<[]>>(\n",
+        ],
+    },
+    Target {
+        day: 16,
+        alphabet: "0123456789ABCDEF",
+        corpus: &[
+            "D2FE28\n", "38006F45291200\n", "EE00D40C823060\n", "8A004A801A8002F478\n", "620080001611562C8802118E34\n",
+            "C0015000016115A2E0802F182340\n", "A0016C880162017C3686B18A3D4780\n", "C200B40A82\n", "04005AC33890\n",
+            "880086C3E88112\n", "CE00C43D881120\n", "D8005AC2A8F0\n", "F600BC2D8F\n", "9C005AC2F8F0\n",
+            "9C0141080250320F1802104A08\n",
+        ],
+    },
+    Target {
+        day: 18,
+        alphabet: "[],0123456789",
+        corpus: &[
+            "[1,1]\n[2,2]\n[3,3]\n[4,4]\n[5,5]\n[6,6]\n",
+            "[[[[4,3],4],4],[7,[[8,4],9]]]\n[1,1]\n",
+            "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]\n[[[5,[2,8]],4],[5,[[9,9],0]]]\n\
+             [6,[[[6,2],[5,6]],[[7,6],[4,7]]]]\n[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]\n",
+        ],
+    },
+];
+
+pub fn targets() -> &'static [Target] {
+    &TARGETS
+}
+
+pub fn target(day: u32) -> Option<&'static Target> {
+    TARGETS.iter().find(|target| target.day == day)
+}
+
+impl Target {
+    // A corpus entry with a few random edits
+    pub fn mutate(&self, rng: &mut Rng) -> String {
+        let alphabet: Vec<char> = self.alphabet.chars().collect();
+        let mut input: Vec<char> = rng.pick(self.corpus).chars().collect();
+        for _ in 0..1 + rng.below(4) {
+            let at = rng.below(input.len() + 1);
+            match rng.below(6) {
+                // Truncated inputs are what the parsers used to get wrong
+                0 => input.truncate(at),
+                1 if at < input.len() => { input.remove(at); },
+                2 => input.insert(at, *rng.pick(&alphabet)),
+                3 if at < input.len() => input[at] = *rng.pick(&alphabet),
+                // Repeating a piece nests brackets and packets deeper than any example does
+                4 => {
+                    let start = rng.below(input.len() + 1);
+                    let end = start + rng.below((input.len() - start).min(32) + 1);
+                    let piece: Vec<char> = input[start..end].to_vec();
+                    for _ in 0..1 + rng.below(8) {
+                        input.splice(at..at, piece.iter().copied());
+                    }
+                },
+                _ => input.insert(at, char::from(rng.below(128) as u8)),
+            }
+        }
+        input.into_iter().collect()
+    }
+
+    // Solves `runs` mutated inputs and stops at the first one that panics
+    pub fn run(&self, seed: u64, runs: usize) -> Option<Crash> {
+        let day = get_day(self.day).unwrap();
+        let mut rng = Rng::new(seed);
+        for _ in 0..runs {
+            let input = self.mutate(&mut rng);
+            if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| day.solve_input(&input))) {
+                let message = payload.downcast_ref::<&str>().map(|message| message.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "Solution panicked".to_string());
+                return Some(Crash { input, message });
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corpus_solves_cleanly() {
+        for target in targets() {
+            for input in target.corpus {
+                assert!(get_day(target.day).unwrap().solve_input(input).is_ok(), "day {}: {:?}", target.day, input);
+            }
+        }
+    }
+
+    #[test]
+    fn parsers_survive_mutated_examples() {
+        for target in targets() {
+            assert_eq!(None, target.run(2021, 500), "day {}", target.day);
+        }
+    }
+}
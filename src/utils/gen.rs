@@ -0,0 +1,584 @@
+use std::{collections::BTreeSet, fmt::Write, ops::RangeInclusive, panic::{self, AssertUnwindSafe}};
+
+use itertools::Itertools;
+
+use crate::{get_day, solution::Part};
+use crate::utils::{coordinates::{Rotation, Vector}, ocr};
+
+// SplitMix64, small and good enough for puzzle inputs. The same seed gives the same input everywhere.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Anything in 0..bound, which has to be positive
+    pub fn below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "there is nothing below 0 to pick");
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        start + (self.next_u64() % (end - start + 1) as u64) as i64
+    }
+
+    pub fn chance(&mut self, percent: usize) -> bool {
+        self.below(100) < percent
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in (1..items.len()).rev() {
+            items.swap(idx, self.below(idx + 1));
+        }
+    }
+}
+
+// Runs a property against the cases seeded 0..cases. A failing case names its seed, so Rng::new(seed) replays it.
+pub fn check(cases: u64, mut property: impl FnMut(&mut Rng)) {
+    for seed in 0..cases {
+        let mut rng = Rng::new(seed);
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| property(&mut rng))) {
+            let message = payload.downcast_ref::<&str>().copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("no message");
+            panic!("the property failed for seed {}: {}", seed, message);
+        }
+    }
+}
+
+// What size means is up to every day, default_size gives inputs about as large as the real ones
+pub struct Generator {
+    pub day: u32,
+    pub default_size: usize,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+impl Generator {
+    pub fn generate(&self, seed: u64, size: usize) -> String {
+        // So that two days with the same seed don't start from the same numbers
+        let mut rng = Rng::new(seed ^ ((self.day as u64) << 56));
+        (self.generate)(&mut rng, size)
+    }
+}
+
+// Days 23 and 24 only know the answers to one particular input, so a generated one couldn't be checked
+static GENERATORS: [Generator; 23] = [
+    Generator { day: 1, default_size: 2000, generate: soundings },
+    Generator { day: 2, default_size: 1000, generate: commands },
+    Generator { day: 3, default_size: 1000, generate: diagnostics },
+    Generator { day: 4, default_size: 100, generate: bingo },
+    Generator { day: 5, default_size: 500, generate: vents },
+    Generator { day: 6, default_size: 300, generate: lanternfish },
+    Generator { day: 7, default_size: 1000, generate: crabs },
+    Generator { day: 8, default_size: 200, generate: displays },
+    Generator { day: 9, default_size: 100, generate: height_map },
+    Generator { day: 10, default_size: 100, generate: navigation },
+    Generator { day: 11, default_size: 10, generate: octopuses },
+    Generator { day: 12, default_size: 6, generate: caves },
+    Generator { day: 13, default_size: 8, generate: origami },
+    Generator { day: 14, default_size: 20, generate: polymer },
+    Generator { day: 15, default_size: 100, generate: chitons },
+    Generator { day: 16, default_size: 60, generate: transmission },
+    Generator { day: 17, default_size: 100, generate: target_area },
+    Generator { day: 18, default_size: 100, generate: snailfish },
+    Generator { day: 19, default_size: 12, generate: scanners },
+    Generator { day: 20, default_size: 100, generate: trench_map },
+    Generator { day: 21, default_size: 0, generate: dirac_dice },
+    Generator { day: 22, default_size: 420, generate: cuboids },
+    Generator { day: 25, default_size: 137, generate: sea_cucumbers },
+];
+
+pub fn generator(day: u32) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|generator| generator.day == day)
+}
+
+// One line per item, each ending in a newline like the real inputs
+fn lines(count: usize, mut line: impl FnMut(usize) -> String) -> String {
+    (0..count).map(|idx| line(idx) + "\n").collect()
+}
+
+fn grid(rng: &mut Rng, rows: usize, columns: usize, cells: &[char]) -> String {
+    lines(rows, |_| (0..columns).map(|_| *rng.pick(cells)).collect())
+}
+
+const DIGITS: [char; 10] = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+
+// Day 1: size depths, drifting deeper on average
+fn soundings(rng: &mut Rng, size: usize) -> String {
+    let mut depth = rng.range(100..=200);
+    lines(size, |_| {
+        depth = (depth + rng.range(-10..=15)).max(0);
+        depth.to_string()
+    })
+}
+
+// Day 2: size commands
+fn commands(rng: &mut Rng, size: usize) -> String {
+    lines(size, |_| format!("{} {}", rng.pick(&["forward", "down", "up"]), rng.range(1..=9)))
+}
+
+// Day 3: size different 12 bit numbers, at most all 4096 of them. Duplicates could make part 2 unsolvable.
+fn diagnostics(rng: &mut Rng, size: usize) -> String {
+    let mut numbers: Vec<usize> = (0..1 << 12).collect();
+    rng.shuffle(&mut numbers);
+    lines(size.min(numbers.len()), |idx| format!("{:012b}", numbers[idx]))
+}
+
+// Day 4: size boards, every number gets drawn so all of them win eventually
+fn bingo(rng: &mut Rng, size: usize) -> String {
+    let mut numbers: Vec<usize> = (0..100).collect();
+    rng.shuffle(&mut numbers);
+    let mut input = numbers.iter().join(",") + "\n";
+    for _ in 0..size {
+        rng.shuffle(&mut numbers);
+        input += "\n";
+        input += &lines(5, |row| numbers[5 * row..5 * row + 5].iter().map(|n| format!("{:>2}", n)).join(" "));
+    }
+    input
+}
+
+// Day 5: size vent lines in a 1000 by 1000 area, horizontal, vertical or diagonal
+fn vents(rng: &mut Rng, size: usize) -> String {
+    lines(size, |_| {
+        let (x1, y1) = (rng.range(0..=999), rng.range(0..=999));
+        let length = rng.range(1..=300);
+        let (dx, dy) = *rng.pick(&[(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (1, -1), (-1, 1), (-1, -1)]);
+        // Shortened where it would leave the area, which keeps it straight
+        let length = [(x1, dx), (y1, dy)].iter()
+            .map(|&(start, delta)| match delta {
+                1 => length.min(999 - start),
+                -1 => length.min(start),
+                _ => length,
+            })
+            .min()
+            .unwrap();
+        format!("{},{} -> {},{}", x1, y1, x1 + dx * length, y1 + dy * length)
+    })
+}
+
+// Day 6: size fish
+fn lanternfish(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1)).map(|_| rng.range(1..=5)).join(",") + "\n"
+}
+
+// Day 7: size crabs, spread over twice as many positions
+fn crabs(rng: &mut Rng, size: usize) -> String {
+    let spread = 2 * size.max(1) as i64;
+    (0..size.max(1)).map(|_| rng.range(0..=spread)).join(",") + "\n"
+}
+
+// Day 8: size displays, each with its own wiring
+fn displays(rng: &mut Rng, size: usize) -> String {
+    const SEGMENTS: [&str; 10] = ["abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg"];
+    lines(size, |_| {
+        let mut wires: Vec<char> = ('a'..='g').collect();
+        rng.shuffle(&mut wires);
+        let mut patterns: Vec<usize> = (0..10).collect();
+        rng.shuffle(&mut patterns);
+        let output: Vec<usize> = (0..4).map(|_| rng.below(10)).collect();
+        let mut wire = |digit: usize| {
+            let mut segments: Vec<char> = SEGMENTS[digit].bytes().map(|segment| wires[(segment - b'a') as usize]).collect();
+            rng.shuffle(&mut segments);
+            segments.into_iter().collect::<String>()
+        };
+        let patterns = patterns.into_iter().map(&mut wire).join(" ");
+        let output = output.into_iter().map(&mut wire).join(" ");
+        format!("{} | {}", patterns, output)
+    })
+}
+
+// Day 9: size by size heights. Every cell belongs to the nearest of about one low point per 40 cells, and
+// rises with the distance to it. Where basins meet there is a wall of 9s.
+fn height_map(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let low_points: Vec<(usize, usize)> = (0..(size * size / 40).max(1))
+        .map(|_| (rng.below(size), rng.below(size)))
+        .collect();
+    let nearest = |row: usize, column: usize| low_points.iter()
+        .enumerate()
+        .map(|(idx, &(low_row, low_column))| (row.abs_diff(low_row) + column.abs_diff(low_column), idx))
+        .min()
+        .unwrap();
+    lines(size, |row| (0..size)
+        .map(|column| {
+            let (distance, basin) = nearest(row, column);
+            let border = [(row + 1, column), (row, column + 1)].into_iter()
+                .any(|(row, column)| row < size && column < size && nearest(row, column).1 != basin);
+            if border { '9' } else { DIGITS[distance.min(8)] }
+        })
+        .collect())
+}
+
+// Day 10: size lines of chunks, a third of them corrupted and the rest incomplete
+fn navigation(rng: &mut Rng, size: usize) -> String {
+    const OPEN: [char; 4] = ['(', '[', '{', '<'];
+    const CLOSE: [char; 4] = [')', ']', '}', '>'];
+    lines(size.max(1), |idx| {
+        let mut line = String::new();
+        let mut open = Vec::new();
+        // The first line is incomplete, so part 2 always has something to complete
+        let corrupted = idx > 0 && rng.chance(33);
+        let length = rng.range(20..=110);
+        for step in 0..length {
+            // Completion scores grow fivefold per open chunk, real inputs never leave more than about 20 open
+            if open.is_empty() || (open.len() < 20 && rng.chance(55)) {
+                let kind = rng.below(4);
+                open.push(kind);
+                line.push(OPEN[kind]);
+            } else {
+                let kind = open.pop().unwrap();
+                if corrupted && step > length / 2 {
+                    line.push(CLOSE[(kind + 1 + rng.below(3)) % 4]);
+                    return line;
+                }
+                line.push(CLOSE[kind]);
+            }
+        }
+        if open.is_empty() {
+            let kind = rng.below(4);
+            open.push(kind);
+            line.push(OPEN[kind]);
+        }
+        if corrupted {
+            // Never got to close anything past the middle, so it breaks right at the end
+            line.push(CLOSE[(open[open.len() - 1] + 1) % 4]);
+        }
+        line
+    })
+}
+
+// Day 11: size by size octopuses, most of them at 0 so that they soon flash at once. Grids of random digits
+// rarely synchronise once they get larger. If a grid never does, it is tried again with fewer random octopuses,
+// down to all of them at 0, which flash at once after ten steps.
+fn octopuses(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut percent = 10;
+    loop {
+        let input = lines(size, |_| (0..size).map(|_| if rng.chance(percent) { *rng.pick(&DIGITS) } else { '0' }).collect());
+        if percent == 0 || get_day(11).unwrap().solve_part(&input, Part::Two).is_ok() {
+            return input;
+        }
+        percent /= 2;
+    }
+}
+
+// Day 12: size small caves, plus a big cave for every three of them. Big caves are never connected to each
+// other, or there would be endless paths. Caves have one letter names, so there are at most 26 of each.
+fn caves(rng: &mut Rng, size: usize) -> String {
+    let small: Vec<String> = ('a'..='z').take(size.clamp(1, 26)).map(String::from).collect();
+    let big: Vec<String> = ('A'..='Z').take((size / 3).clamp(1, 26)).map(String::from).collect();
+    let mut caves: Vec<&str> = small.iter().chain(&big).map(String::as_str).collect();
+    rng.shuffle(&mut caves);
+    caves.insert(0, "start");
+    caves.push("end");
+
+    let is_big = |cave: &str| cave.chars().all(|c| c.is_ascii_uppercase());
+    let mut edges = BTreeSet::new();
+    let connect = |edges: &mut BTreeSet<(usize, usize)>, a: usize, b: usize| {
+        if a != b && !(is_big(caves[a]) && is_big(caves[b])) {
+            edges.insert((a.min(b), a.max(b)));
+        }
+    };
+    // Every cave is connected to one that came before, so all of them can be reached from start
+    for cave in 1..caves.len() {
+        let mut other = rng.below(cave);
+        while is_big(caves[cave]) && is_big(caves[other]) {
+            other = rng.below(cave);
+        }
+        connect(&mut edges, cave, other);
+    }
+    for _ in 0..caves.len() / 2 {
+        let (a, b) = (1 + rng.below(caves.len() - 1), 1 + rng.below(caves.len() - 1));
+        connect(&mut edges, a, b);
+    }
+    let mut edges: Vec<_> = edges.into_iter().collect();
+    rng.shuffle(&mut edges);
+    edges.into_iter()
+        .map(|(a, b)| if rng.chance(50) { format!("{}-{}\n", caves[a], caves[b]) } else { format!("{}-{}\n", caves[b], caves[a]) })
+        .collect()
+}
+
+// Day 13: a code of size letters, unfolded twelve times. Every dot ends up on one side of the crease or
+// the other, or both, so folding it back up spells the code again.
+fn origami(rng: &mut Rng, size: usize) -> String {
+    let alphabet: Vec<char> = ocr::letters().collect();
+    let code: String = (0..size.max(1)).map(|_| *rng.pick(&alphabet)).collect();
+    let letters = ocr::draw(&code).unwrap();
+    let mut dots: BTreeSet<[i64; 2]> = letters.iter()
+        .filter(|(_, &dot)| dot)
+        .map(|((row, column), _)| [column as i64, row as i64])
+        .collect();
+    let mut size = [letters.column_count() as i64, letters.row_count() as i64];
+
+    let mut folds = Vec::new();
+    for fold in 0..12 {
+        let axis = 1 - fold % 2;
+        let line = size[axis];
+        let mut unfolded = BTreeSet::new();
+        for dot in dots {
+            let mut mirrored = dot;
+            mirrored[axis] = 2 * line - dot[axis];
+            match rng.below(5) {
+                0 | 1 => { unfolded.insert(dot); },
+                2 | 3 => { unfolded.insert(mirrored); },
+                _ => { unfolded.extend([dot, mirrored]); },
+            }
+        }
+        dots = unfolded;
+        size[axis] = 2 * line + 1;
+        folds.push(format!("fold along {}={}\n", if axis == 0 { 'x' } else { 'y' }, line));
+    }
+
+    let mut dots: Vec<_> = dots.into_iter().collect();
+    rng.shuffle(&mut dots);
+    let mut input: String = dots.iter().map(|[x, y]| format!("{},{}\n", x, y)).collect();
+    input += "\n";
+    input.extend(folds.into_iter().rev());
+    input
+}
+
+// Day 14: a template of size elements, with a rule for every pair of the ten elements
+fn polymer(rng: &mut Rng, size: usize) -> String {
+    const ELEMENTS: [char; 10] = ['B', 'C', 'F', 'H', 'K', 'N', 'O', 'P', 'S', 'V'];
+    let mut input: String = (0..size.max(1)).map(|_| *rng.pick(&ELEMENTS)).collect();
+    input += "\n\n";
+    for (a, b) in ELEMENTS.iter().cartesian_product(&ELEMENTS) {
+        writeln!(input, "{}{} -> {}", a, b, rng.pick(&ELEMENTS)).unwrap();
+    }
+    input
+}
+
+// Day 15: size by size risk levels
+fn chitons(rng: &mut Rng, size: usize) -> String {
+    grid(rng, size.max(1), size.max(1), &DIGITS[1..])
+}
+
+// Day 16: a transmission of about size packets
+fn transmission(rng: &mut Rng, size: usize) -> String {
+    let mut bits = String::new();
+    let mut budget = size.max(1);
+    packet(rng, &mut bits, &mut budget, 0);
+    while !bits.len().is_multiple_of(4) {
+        bits.push('0');
+    }
+    bits.as_bytes()
+        .chunks(4)
+        .map(|nibble| format!("{:X}", u8::from_str_radix(std::str::from_utf8(nibble).unwrap(), 2).unwrap()))
+        .collect::<String>() + "\n"
+}
+
+// Appends one packet and returns its value, which stays small enough for 64 bits
+fn packet(rng: &mut Rng, bits: &mut String, budget: &mut usize, depth: usize) -> u64 {
+    *budget = budget.saturating_sub(1);
+    write!(bits, "{:03b}", rng.below(8)).unwrap();
+    if *budget == 0 || depth > 8 || rng.chance(40) {
+        let value = rng.below(1 << 12) as u64;
+        bits.push_str("100");
+        let digits = format!("{:b}", value);
+        let digits = format!("{}{}", "0".repeat((4 - digits.len() % 4) % 4), digits);
+        let groups = digits.len() / 4;
+        for (idx, group) in digits.as_bytes().chunks(4).enumerate() {
+            bits.push(if idx + 1 == groups { '0' } else { '1' });
+            bits.push_str(std::str::from_utf8(group).unwrap());
+        }
+        return value;
+    }
+
+    let type_id = *rng.pick(&[0, 1, 2, 3, 5, 6, 7]);
+    let count = if type_id >= 5 { 2 } else { 1 + rng.below(4) };
+    let mut body = String::new();
+    let values: Vec<u64> = (0..count).map(|_| packet(rng, &mut body, budget, depth + 1)).collect();
+    let value = match type_id {
+        0 => values.iter().try_fold(0_u64, |sum, &value| sum.checked_add(value)),
+        1 => values.iter().try_fold(1_u64, |product, &value| product.checked_mul(value)),
+        2 => values.iter().min().copied(),
+        3 => values.iter().max().copied(),
+        5 => Some((values[0] > values[1]) as u64),
+        6 => Some((values[0] < values[1]) as u64),
+        _ => Some((values[0] == values[1]) as u64),
+    };
+    // Sums and products that grow too large become minimums instead
+    let (type_id, value) = match value {
+        Some(value) if value < 1 << 48 => (type_id, value),
+        _ => (2, *values.iter().min().unwrap()),
+    };
+    write!(bits, "{:03b}", type_id).unwrap();
+    if body.len() < 1 << 15 && rng.chance(50) {
+        write!(bits, "0{:015b}", body.len()).unwrap();
+    } else {
+        write!(bits, "1{:011b}", count).unwrap();
+    }
+    bits.push_str(&body);
+    value
+}
+
+// Day 17: a target about size steps away, to the right and below
+fn target_area(rng: &mut Rng, size: usize) -> String {
+    // Part 1 tries upwards velocities below 1000, so the target can't be much deeper than that
+    let size = size.clamp(2, 600) as i64;
+    let left = rng.range(size / 2..=size);
+    let top = -rng.range(size / 2..=size);
+    format!("target area: x={}..{}, y={}..{}\n", left, left + rng.range(size / 4..=size / 2), top - rng.range(size / 4..=size / 2), top)
+}
+
+// Day 18: size reduced snailfish numbers
+fn snailfish(rng: &mut Rng, size: usize) -> String {
+    fn pair(rng: &mut Rng, depth: usize) -> String {
+        let element = |rng: &mut Rng| if depth < 4 && rng.chance(60) { pair(rng, depth + 1) } else { rng.below(10).to_string() };
+        let left = element(rng);
+        format!("[{},{}]", left, element(rng))
+    }
+    lines(size.max(1), |_| pair(rng, 1))
+}
+
+// Day 19: size scanners, each of them overlapping with one that came before in at least twelve beacons
+fn scanners(rng: &mut Rng, size: usize) -> String {
+    const RANGE: i64 = 1000;
+    let mut positions = vec![Vector::<isize, 3>::zero()];
+    let mut beacons = BTreeSet::new();
+    let reach = Vector::from((RANGE as isize, RANGE as isize, RANGE as isize));
+    add_beacons(rng, &mut beacons, -reach, reach, 14);
+    for _ in 1..size.max(1) {
+        let parent = *rng.pick(&positions);
+        let position = parent + Vector::<isize, 3>::zero().map(|_| rng.range(-1200..=1200) as isize);
+        let low = (parent - reach).zip_with(position - reach, isize::max);
+        let high = (parent + reach).zip_with(position + reach, isize::min);
+        add_beacons(rng, &mut beacons, low, high, 12);
+        add_beacons(rng, &mut beacons, position - reach, position + reach, 14);
+        positions.push(position);
+    }
+
+    // Every scanner sees what is within its range, turned whichever way it faces
+    let rotations = Rotation::all();
+    let mut input = String::new();
+    for (idx, &position) in positions.iter().enumerate() {
+        let rotation = *rng.pick(&rotations);
+        let mut seen: Vec<_> = beacons.iter()
+            .filter(|&&beacon| beacon.chebyshev_distance(position) <= RANGE as isize)
+            .map(|&beacon| rotation * (beacon - position))
+            .collect();
+        rng.shuffle(&mut seen);
+        if idx > 0 {
+            input += "\n";
+        }
+        writeln!(input, "--- scanner {} ---", idx).unwrap();
+        for beacon in seen {
+            writeln!(input, "{},{},{}", beacon.x(), beacon.y(), beacon.z()).unwrap();
+        }
+    }
+    input
+}
+
+// count more beacons somewhere between the corners low and high
+fn add_beacons(rng: &mut Rng, beacons: &mut BTreeSet<Vector<isize, 3>>, low: Vector<isize, 3>, high: Vector<isize, 3>, count: usize) {
+    let goal = beacons.len() + count;
+    while beacons.len() < goal {
+        beacons.insert(low.zip_with(high, |low, high| rng.range(low as i64..=high as i64) as isize));
+    }
+}
+
+// Day 20: an algorithm and a size by size image. If empty space lights up, full space has to go dark
+// again, or there would be infinitely many lit pixels.
+fn trench_map(rng: &mut Rng, size: usize) -> String {
+    let mut algorithm: Vec<char> = (0..512).map(|_| *rng.pick(&['#', '.'])).collect();
+    if algorithm[0] == '#' {
+        algorithm[511] = '.';
+    }
+    let mut input: String = algorithm.into_iter().collect();
+    input += "\n\n";
+    input += &grid(rng, size.max(1), size.max(1), &['#', '.']);
+    input
+}
+
+// Day 21: both starting positions, size doesn't matter
+fn dirac_dice(rng: &mut Rng, _size: usize) -> String {
+    lines(2, |idx| format!("Player {} starting position: {}", idx + 1, rng.range(1..=10)))
+}
+
+// Day 22: size cuboids, most of them in the initialization area and the first one always on
+fn cuboids(rng: &mut Rng, size: usize) -> String {
+    let initialization = size * 3 / 4;
+    lines(size.max(1), |idx| {
+        let (reach, extent) = if idx < initialization.max(1) { (50, 40) } else { (90_000, 40_000) };
+        let state = if idx == 0 || rng.chance(60) { "on" } else { "off" };
+        let mut range = || {
+            let start = rng.range(-reach..=reach - extent / 4);
+            let end = (start + rng.range(extent / 8..=extent)).min(reach);
+            format!("{}..{}", start, end)
+        };
+        let (x, y, z) = (range(), range(), range());
+        format!("{} x={},y={},z={}", state, x, y, z)
+    })
+}
+
+// Day 25: size by size spots, a third of them east facing and a third south facing sea cucumbers
+fn sea_cucumbers(rng: &mut Rng, size: usize) -> String {
+    grid(rng, size.max(1), size.max(1), &['>', 'v', '.'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+
+        let mut rng = Rng::new(1);
+        assert!((0..100).map(|_| rng.range(-3..=3)).all(|n| (-3..=3).contains(&n)));
+        let mut items: Vec<usize> = (0..20).collect();
+        rng.shuffle(&mut items);
+        assert_ne!((0..20).collect::<Vec<_>>(), items);
+        items.sort_unstable();
+        assert_eq!((0..20).collect::<Vec<_>>(), items);
+    }
+
+    #[test]
+    fn check_replays_every_seed() {
+        let mut seeds = Vec::new();
+        check(10, |rng| seeds.push(rng.next_u64()));
+        assert_eq!((0..10).map(|seed| Rng::new(seed).next_u64()).collect::<Vec<_>>(), seeds);
+        let payload = panic::catch_unwind(|| check(10, |rng| assert!(rng.below(4) != 0, "drew 0"))).unwrap_err();
+        let message = payload.downcast_ref::<String>().unwrap();
+        assert!(message.starts_with("the property failed for seed ") && message.ends_with(": drew 0"), "{}", message);
+    }
+
+    #[test]
+    fn large_octopus_grids_synchronise() {
+        for seed in 0..3 {
+            let input = generator(11).unwrap().generate(seed, 40);
+            assert!(get_day(11).unwrap().solve_part(&input, Part::Two).is_ok(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn every_day_but_23_and_24_has_a_generator() {
+        for day in (1..=25).filter(|day| ![23, 24].contains(day)) {
+            let generator = generator(day).unwrap();
+            let input = generator.generate(2021, generator.default_size.min(5));
+            assert!(input.ends_with('\n'), "day {}", day);
+            assert_eq!(input, generator.generate(2021, generator.default_size.min(5)));
+        }
+        assert!(generator(23).is_none() && generator(24).is_none() && generator(26).is_none());
+    }
+}
@@ -0,0 +1,274 @@
+use std::{ops::{Index, IndexMut}, slice};
+
+use crate::error::Result;
+use crate::utils::{coordinates::Vector, parse};
+
+// Positions are (row, column) with row 0 at the top, Vectors index as x = column and y = row
+pub type Position = (usize, usize);
+// Rows and columns to move by, e.g. (1, 0) is one row down
+pub type Offset = (isize, isize);
+
+pub const ORTHOGONAL: [Offset; 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+pub const ADJACENT: [Offset; 8] = [(-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (1, -1), (0, -1), (-1, -1)];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    columns: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn from_vec(cells: Vec<T>, (rows, columns): (usize, usize)) -> Self {
+        assert_eq!(rows * columns, cells.len(), "a {}x{} grid needs {} cells", rows, columns, rows * columns);
+        Grid { cells, rows, columns }
+    }
+
+    pub fn from_vecvec(vecvec: Vec<Vec<T>>) -> Self {
+        let rows = vecvec.len();
+        let columns = vecvec.first().map_or(0, Vec::len);
+        assert!(vecvec.iter().all(|row| row.len() == columns), "all rows of a grid need {} columns", columns);
+        Grid { cells: vecvec.into_iter().flatten().collect(), rows, columns }
+    }
+
+    // One cell per character, see parse::grid
+    pub fn parse(input: &str, expected: &str, cell: impl FnMut(char) -> Option<T>) -> Result<Self> {
+        parse::grid(input, expected, cell).map(Self::from_vecvec)
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.rows
+    }
+
+    pub fn column_count(&self) -> usize {
+        self.columns
+    }
+
+    pub fn contains(&self, (row, column): Position) -> bool {
+        row < self.rows && column < self.columns
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.offset(position).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.offset(position).map(move |offset| &mut self.cells[offset])
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.columns..(row + 1) * self.columns]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.columns..(row + 1) * self.columns]
+    }
+
+    pub fn rows(&self) -> slice::ChunksExact<'_, T> {
+        self.cells.chunks_exact(self.columns.max(1))
+    }
+
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(column < self.columns, "column {} outside of a grid with {} columns", column, self.columns);
+        self.cells[column..].iter().step_by(self.columns)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.columns).map(move |column| self.column(column))
+    }
+
+    // Row by row, left to right
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let columns = self.columns;
+        (0..self.rows * columns).map(move |offset| (offset / columns, offset % columns))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Position, &mut T)> + '_ {
+        self.positions().zip(self.cells.iter_mut())
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    // Same shape, every cell converted on its own
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { cells: self.cells.iter().map(f).collect(), rows: self.rows, columns: self.columns }
+    }
+
+    // None if the step leaves the grid
+    pub fn step(&self, (row, column): Position, (rows, columns): Offset) -> Option<Position> {
+        let position = (row.checked_add_signed(rows)?, column.checked_add_signed(columns)?);
+        self.contains(position).then_some(position)
+    }
+
+    // Leaving the grid on one side enters it again on the opposite one
+    pub fn wrapping_step(&self, (row, column): Position, (rows, columns): Offset) -> Position {
+        (
+            (row as isize + rows).rem_euclid(self.rows as isize) as usize,
+            (column as isize + columns).rem_euclid(self.columns as isize) as usize,
+        )
+    }
+
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = (Position, &T)> + '_ {
+        self.neighbours(position, &ORTHOGONAL)
+    }
+
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = (Position, &T)> + '_ {
+        self.neighbours(position, &ADJACENT)
+    }
+
+    // On grids narrower than three cells the same neighbour shows up more than once
+    pub fn wrapping_neighbours4(&self, position: Position) -> impl Iterator<Item = (Position, &T)> + '_ {
+        self.wrapping_neighbours(position, &ORTHOGONAL)
+    }
+
+    pub fn wrapping_neighbours8(&self, position: Position) -> impl Iterator<Item = (Position, &T)> + '_ {
+        self.wrapping_neighbours(position, &ADJACENT)
+    }
+
+    fn neighbours(&self, position: Position, offsets: &'static [Offset]) -> impl Iterator<Item = (Position, &T)> + '_ {
+        offsets.iter()
+            .filter_map(move |&offset| self.step(position, offset))
+            .map(move |neighbour| (neighbour, &self[neighbour]))
+    }
+
+    fn wrapping_neighbours(&self, position: Position, offsets: &'static [Offset]) -> impl Iterator<Item = (Position, &T)> + '_ {
+        offsets.iter()
+            .map(move |&offset| self.wrapping_step(position, offset))
+            .map(move |neighbour| (neighbour, &self[neighbour]))
+    }
+
+    fn offset(&self, (row, column): Position) -> Option<usize> {
+        self.contains((row, column)).then(|| row * self.columns + column)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(rows: usize, columns: usize, value: T) -> Self {
+        Grid { cells: vec![value; rows * columns], rows, columns }
+    }
+}
+
+impl Grid<u32> {
+    pub fn parse_digits(input: &str) -> Result<Self> {
+        Self::parse(input, "a digit", parse::digit)
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        match self.get(position) {
+            Some(cell) => cell,
+            None => panic!("{:?} is outside of a {}x{} grid", position, self.rows, self.columns),
+        }
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        let (rows, columns) = (self.rows, self.columns);
+        match self.get_mut(position) {
+            Some(cell) => cell,
+            None => panic!("{:?} is outside of a {}x{} grid", position, rows, columns),
+        }
+    }
+}
+
+impl<T> Index<Vector<usize, 2>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, idx: Vector<usize, 2>) -> &Self::Output {
+        &self[(idx.y(), idx.x())]
+    }
+}
+
+impl<T> IndexMut<Vector<usize, 2>> for Grid<T> {
+    fn index_mut(&mut self, idx: Vector<usize, 2>) -> &mut Self::Output {
+        &mut self[(idx.y(), idx.x())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TESTDATA_ARR: [&str; 25] = [
+        "1", "2", "3", "4", "5",
+        "6", "7", "8", "9", "10",
+        "11", "12", "13", "14", "15",
+        "16", "17", "18", "19", "20",
+        "21", "22", "23", "24", "25",
+    ];
+
+
+    #[test]
+    fn indexing_yields_ref() {
+        let test_grid = Grid::from_vec(TESTDATA_ARR.to_vec(), (5, 5));
+        assert_eq!("1", test_grid[(0, 0)]);
+    }
+
+    #[test]
+    fn indexing_yields_ref_next_row() {
+        let test_grid = Grid::from_vec(TESTDATA_ARR.to_vec(), (5, 5));
+        assert_eq!("19", test_grid[(3, 3)]);
+    }
+
+    #[test]
+    fn indexing_mut_enables_mutability() {
+        let mut test_grid = Grid::from_vec(TESTDATA_ARR.to_vec(), (5, 5));
+        assert_eq!("1", test_grid[(0, 0)]);
+        test_grid[(0, 0)] = "2";
+        assert_eq!("2", test_grid[(0, 0)]);
+    }
+
+    #[test]
+    fn non_square_grids_index_by_row_and_column() {
+        let grid = Grid::from_vecvec(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!((2, 3), (grid.row_count(), grid.column_count()));
+        assert_eq!(6, grid[(1, 2)]);
+        assert_eq!(6, grid[Vector::from((2, 1))]);
+        assert_eq!(Some(&4), grid.get((1, 0)));
+        assert_eq!(None, grid.get((0, 3)));
+        assert_eq!(None, grid.get((2, 0)));
+    }
+
+    #[test]
+    fn views_do_not_copy() {
+        let mut grid = Grid::parse_digits("123\n456\n").unwrap();
+        assert_eq!(&[4, 5, 6], grid.row(1));
+        assert_eq!(vec![&2, &5], grid.column(1).collect::<Vec<_>>());
+        assert_eq!(vec![vec![1, 4], vec![2, 5], vec![3, 6]], grid.columns().map(|column| column.copied().collect::<Vec<_>>()).collect::<Vec<_>>());
+        assert_eq!(vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2)], grid.positions().collect::<Vec<_>>());
+
+        *grid.get_mut((0, 2)).unwrap() = 9;
+        assert_eq!(Some(((0, 2), &9)), grid.iter().find(|(_, &cell)| cell > 6));
+        assert!(Grid::parse_digits("12\n3\n").is_err());
+    }
+
+    #[test]
+    fn neighbours_stay_inside_or_wrap_around() {
+        let grid = Grid::parse_digits("123\n456\n").unwrap();
+        let values = |neighbours: Vec<(Position, &u32)>| neighbours.into_iter().map(|(_, &value)| value).collect::<Vec<_>>();
+
+        assert_eq!(vec![2, 4], values(grid.neighbours4((0, 0)).collect()));
+        assert_eq!(vec![2, 6, 4], values(grid.neighbours4((1, 1)).collect()));
+        assert_eq!(vec![2, 5, 4], values(grid.neighbours8((0, 0)).collect()));
+        assert_eq!(vec![((1, 0), &4), ((0, 1), &2), ((1, 0), &4), ((0, 2), &3)], grid.wrapping_neighbours4((0, 0)).collect::<Vec<_>>());
+        assert_eq!(8, grid.wrapping_neighbours8((1, 2)).count());
+
+        assert_eq!(None, grid.step((1, 2), (0, 1)));
+        assert_eq!((1, 0), grid.wrapping_step((1, 2), (0, 1)));
+        assert_eq!((1, 2), grid.wrapping_step((0, 0), (-1, -1)));
+    }
+}
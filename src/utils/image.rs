@@ -0,0 +1,171 @@
+use std::{borrow::Cow, collections::HashMap, fs, path::Path, time::Duration};
+
+use crate::error::{AocError, Result};
+use crate::utils::grid::Grid;
+
+pub type Rgb = [u8; 3];
+// One pixel per cell, scaled up when written
+pub type Image = Grid<Rgb>;
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+pub const NIGHT: Rgb = [15, 15, 35];
+pub const GOLD: Rgb = [255, 200, 40];
+pub const ORANGE: Rgb = [240, 120, 30];
+pub const CYAN: Rgb = [40, 190, 220];
+// Easy to tell apart when regions sit next to each other
+pub const REGIONS: [Rgb; 8] = [
+    [230, 25, 75], [60, 180, 75], [255, 225, 25], [0, 130, 200],
+    [245, 130, 48], [145, 30, 180], [70, 240, 240], [240, 50, 230],
+];
+
+// From black at 0 to white at max
+pub fn grey(level: u32, max: u32) -> Rgb {
+    let value = (level.min(max) * 255 / max.max(1)) as u8;
+    [value, value, value]
+}
+
+pub fn save_png(path: &Path, image: &Image, scale: usize) -> Result<()> {
+    let bytes = encode_png(image, scale).map_err(|error| AocError::Encode(path.to_path_buf(), error))?;
+    fs::write(path, bytes).map_err(|error| AocError::Write(path.to_path_buf(), error))
+}
+
+// Numbered frame0000.png, frame0001.png, ... in directory, which is created if needed
+pub fn save_pngs(directory: &Path, frames: impl IntoIterator<Item = Image>, scale: usize) -> Result<usize> {
+    fs::create_dir_all(directory).map_err(|error| AocError::Write(directory.to_path_buf(), error))?;
+    let mut count = 0;
+    for frame in frames {
+        save_png(&directory.join(format!("frame{:04}.png", count)), &frame, scale)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+// Frames of different sizes are centred on the largest one, with background around them
+pub fn save_gif(path: &Path, frames: &[Image], scale: usize, delay: Duration, background: Rgb) -> Result<()> {
+    let bytes = encode_gif(frames, scale, delay, background).map_err(|error| AocError::Encode(path.to_path_buf(), error))?;
+    fs::write(path, bytes).map_err(|error| AocError::Write(path.to_path_buf(), error))
+}
+
+pub fn encode_png(image: &Image, scale: usize) -> std::result::Result<Vec<u8>, String> {
+    let (width, height, pixels) = scaled(image, scale);
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|error| error.to_string())?;
+    writer.write_image_data(&pixels.concat()).map_err(|error| error.to_string())?;
+    writer.finish().map_err(|error| error.to_string())?;
+    Ok(bytes)
+}
+
+pub fn encode_gif(frames: &[Image], scale: usize, delay: Duration, background: Rgb) -> std::result::Result<Vec<u8>, String> {
+    let rows = frames.iter().map(Grid::row_count).max().unwrap_or(0);
+    let columns = frames.iter().map(Grid::column_count).max().unwrap_or(0);
+    let (width, height) = (columns * scale, rows * scale);
+    if frames.is_empty() || width == 0 || height == 0 {
+        return Err("there is nothing to draw".to_string());
+    }
+    if width > u16::MAX as usize || height > u16::MAX as usize {
+        return Err(format!("{}x{} pixels is too large for a GIF, try a smaller scale", width, height));
+    }
+
+    // GIFs hold at most 256 colours, which grid states get nowhere near
+    let mut palette = HashMap::from([(background, 0)]);
+    for frame in frames {
+        for &colour in frame.cells() {
+            let next = palette.len();
+            palette.entry(colour).or_insert(next);
+        }
+    }
+    if palette.len() > 256 {
+        return Err(format!("the frames use {} colours, a GIF can only hold 256", palette.len()));
+    }
+    let mut colours = vec![BLACK; palette.len()];
+    for (&colour, &idx) in &palette {
+        colours[idx] = colour;
+    }
+
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = gif::Encoder::new(&mut bytes, width as u16, height as u16, &colours.concat())
+            .map_err(|error| error.to_string())?;
+        encoder.set_repeat(gif::Repeat::Infinite).map_err(|error| error.to_string())?;
+        for frame in frames {
+            let (_, _, pixels) = scaled(&centred(frame, rows, columns, background), scale);
+            let gif_frame = gif::Frame {
+                width: width as u16,
+                height: height as u16,
+                delay: (delay.as_millis() / 10).min(u16::MAX as u128) as u16,
+                buffer: Cow::Owned(pixels.iter().map(|colour| palette[colour] as u8).collect()),
+                ..gif::Frame::default()
+            };
+            encoder.write_frame(&gif_frame).map_err(|error| error.to_string())?;
+        }
+    }
+    Ok(bytes)
+}
+
+fn centred(image: &Image, rows: usize, columns: usize, background: Rgb) -> Image {
+    let mut canvas = Grid::new(rows, columns, background);
+    let (top, left) = ((rows - image.row_count()) / 2, (columns - image.column_count()) / 2);
+    for ((row, column), &colour) in image.iter() {
+        canvas[(top + row, left + column)] = colour;
+    }
+    canvas
+}
+
+// Every cell becomes a square of scale by scale pixels, row by row
+fn scaled(image: &Image, scale: usize) -> (usize, usize, Vec<Rgb>) {
+    let scale = scale.max(1);
+    let mut pixels = Vec::with_capacity(image.cells().len() * scale * scale);
+    for row in image.rows() {
+        let line: Vec<Rgb> = row.iter().flat_map(|&colour| [colour].repeat(scale)).collect();
+        for _ in 0..scale {
+            pixels.extend_from_slice(&line);
+        }
+    }
+    (image.column_count() * scale, image.row_count() * scale, pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkers() -> Image {
+        Grid::from_vecvec(vec![vec![WHITE, BLACK, WHITE], vec![BLACK, GOLD, BLACK]])
+    }
+
+    #[test]
+    fn pngs_hold_the_scaled_cells() {
+        let bytes = encode_png(&checkers(), 2).unwrap();
+        let mut reader = png::Decoder::new(bytes.as_slice()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((6, 4), (info.width, info.height));
+        assert_eq!(&[255, 255, 255, 255, 255, 255, 0, 0, 0], &pixels[..9]);
+        assert_eq!(&GOLD[..], &pixels[(2 * 6 + 2) * 3..(2 * 6 + 3) * 3]);
+    }
+
+    #[test]
+    fn gifs_centre_smaller_frames() {
+        let frames = vec![checkers(), Grid::new(1, 1, CYAN)];
+        let bytes = encode_gif(&frames, 1, Duration::from_millis(50), NIGHT).unwrap();
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(bytes.as_slice()).unwrap();
+        assert_eq!((3, 2), (decoder.width(), decoder.height()));
+
+        let first = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(5, first.delay);
+        assert_eq!(&[255, 255, 255, 255], &first.buffer[..4]);
+        let second = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(&[15, 15, 35, 255], &second.buffer[..4]);
+        assert_eq!(&[40, 190, 220, 255], &second.buffer[4..8]);
+        assert!(decoder.read_next_frame().unwrap().is_none());
+
+        assert!(encode_gif(&[], 1, Duration::ZERO, BLACK).is_err());
+        let noise = Grid::from_vec((0..300).map(|value| [value as u8, (value / 256) as u8, 0]).collect(), (1, 300));
+        assert!(encode_gif(&[noise], 1, Duration::ZERO, BLACK).is_err());
+    }
+}
@@ -0,0 +1,377 @@
+use std::{env, fs::{self, File}, io::{self, BufRead, BufReader, Cursor, Read, Write}, net::TcpStream, path::{Path, PathBuf}};
+
+use aoc_downloader::download_day;
+
+use crate::error::{AocError, Result};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    // The downloaded inputs in the input directory
+    Cache,
+    File(PathBuf),
+    Stdin,
+    Embedded(String),
+    // Downloads into the input directory, so the next lookup is served from the cache
+    Download,
+    // Same as Download, but from a plain http:// stand-in for adventofcode.com
+    Server { url: String, session: Option<String> },
+}
+
+#[derive(Clone, Debug)]
+pub struct InputProvider {
+    directory: PathBuf,
+    sources: Vec<InputSource>,
+    offline: bool,
+    profile: Option<String>,
+}
+
+impl Default for InputProvider {
+    fn default() -> Self {
+        InputProvider::new("input")
+    }
+}
+
+impl InputProvider {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        InputProvider {
+            directory: directory.into(),
+            sources: vec![InputSource::Cache, InputSource::Download],
+            offline: env::var_os("AOC_OFFLINE").is_some(),
+            profile: None,
+        }
+    }
+
+    // Inputs of a profile live in <directory>/<profile>/dayNN.txt, next to its answers.json
+    pub fn with_profile(mut self, profile: &str) -> Result<Self> {
+        if !is_valid_profile(profile) {
+            return Err(AocError::Profile(profile.to_string()));
+        }
+        self.profile = Some(profile.to_string());
+        Ok(self)
+    }
+
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    // Offline providers skip every source that would need the network
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    pub fn with_sources(mut self, sources: Vec<InputSource>) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    pub fn sources(&self) -> &[InputSource] {
+        &self.sources
+    }
+
+    pub fn cache_path(&self, day: u32) -> PathBuf {
+        match &self.profile {
+            Some(profile) => self.directory.join(profile).join(format!("day{:02}.txt", day)),
+            None => self.directory.join(format!("input{}.txt", day)),
+        }
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        match &self.profile {
+            Some(profile) => self.directory.join(profile).join("answers.json"),
+            None => self.directory.join("answers.json"),
+        }
+    }
+
+    // Tries the sources in order, the first one that has an input for the day wins
+    pub fn load(&self, day: u32) -> Result<String> {
+        for source in &self.sources {
+            if self.offline && source.needs_network() {
+                continue;
+            }
+            if let Some(input) = self.load_from(source, day)? {
+                return Ok(input);
+            }
+        }
+        Err(self.missing(day))
+    }
+
+    // Same lookup as load, but files and stdin are read as they are consumed instead of all at once
+    pub fn reader(&self, day: u32) -> Result<InputReader> {
+        for source in &self.sources {
+            if self.offline && source.needs_network() {
+                continue;
+            }
+            if let Some(reader) = self.open_from(source, day)? {
+                return Ok(reader);
+            }
+        }
+        Err(self.missing(day))
+    }
+
+    pub fn lines(&self, day: u32) -> Result<impl Iterator<Item = Result<String>>> {
+        Ok(self.reader(day)?.lines())
+    }
+
+    fn missing(&self, day: u32) -> AocError {
+        if self.offline {
+            AocError::Offline(day, self.cache_path(day))
+        } else {
+            AocError::Missing(day, self.cache_path(day))
+        }
+    }
+
+    fn open_from(&self, source: &InputSource, day: u32) -> Result<Option<InputReader>> {
+        match source {
+            InputSource::Cache => {
+                let path = self.cache_path(day);
+                if path.exists() {
+                    InputReader::open(&path).map(Some)
+                } else {
+                    Ok(None)
+                }
+            },
+            InputSource::File(path) => InputReader::open(path).map(Some),
+            InputSource::Stdin => Ok(Some(InputReader::new("<stdin>", BufReader::new(io::stdin())))),
+            // Downloads end up in memory anyway, they are nowhere near large enough to matter
+            _ => Ok(self.load_from(source, day)?.map(InputReader::from_string)),
+        }
+    }
+
+    fn load_from(&self, source: &InputSource, day: u32) -> Result<Option<String>> {
+        match source {
+            InputSource::Cache => {
+                let path = self.cache_path(day);
+                if path.exists() {
+                    read_file(&path).map(Some)
+                } else {
+                    Ok(None)
+                }
+            },
+            InputSource::File(path) => read_file(path).map(Some),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)
+                    .map_err(|error| AocError::Io(PathBuf::from("<stdin>"), error))?;
+                Ok(Some(input))
+            },
+            InputSource::Embedded(input) => Ok(Some(input.clone())),
+            // The downloader only knows the session of the default profile
+            InputSource::Download if self.profile.is_some() => Ok(None),
+            InputSource::Download => {
+                self.create_directory()?;
+                download_day(day, &self.directory.to_string_lossy())
+                    .map_err(|error| AocError::Download(day, error.to_string()))?;
+                read_file(&self.cache_path(day)).map(Some)
+            },
+            InputSource::Server { url, session } => {
+                self.create_directory()?;
+                let url = format!("{}/2021/day/{}/input", url.trim_end_matches('/'), day);
+                let input = http_get(&url, session.as_deref())
+                    .map_err(|error| AocError::Download(day, error))?;
+                let path = self.cache_path(day);
+                fs::create_dir_all(path.parent().unwrap())
+                    .map_err(|error| AocError::Io(path.clone(), error))?;
+                fs::write(&path, &input).map_err(|error| AocError::Io(path, error))?;
+                Ok(Some(input))
+            },
+        }
+    }
+
+    fn create_directory(&self) -> Result<()> {
+        fs::create_dir_all(&self.directory)
+            .map_err(|error| AocError::Io(self.directory.clone(), error))
+    }
+}
+
+// A buffered reader over the input of a day, errors name where the input came from
+pub struct InputReader {
+    path: PathBuf,
+    reader: Box<dyn BufRead>,
+}
+
+impl InputReader {
+    pub fn new(path: impl Into<PathBuf>, reader: impl BufRead + 'static) -> Self {
+        InputReader { path: path.into(), reader: Box::new(reader) }
+    }
+
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = File::open(path).map_err(|error| AocError::Io(path.to_path_buf(), error))?;
+        Ok(InputReader::new(path, BufReader::new(file)))
+    }
+
+    pub fn from_string(input: String) -> Self {
+        InputReader::new("<memory>", Cursor::new(input.into_bytes()))
+    }
+
+    // Only ever holds one line in memory, without its line ending
+    pub fn lines(self) -> impl Iterator<Item = Result<String>> {
+        let path = self.path;
+        self.reader.lines()
+            .map(move |line| line.map_err(|error| AocError::Io(path.clone(), error)))
+    }
+
+    // Same for inputs that are one long line of fields, the line ending stays on the last field
+    pub fn split(self, separator: u8) -> impl Iterator<Item = Result<String>> {
+        let path = self.path;
+        self.reader.split(separator)
+            .map(move |field| field
+                .map_err(|error| AocError::Io(path.clone(), error))
+                .and_then(|field| String::from_utf8(field)
+                    .map_err(|error| AocError::Io(path.clone(), io::Error::new(io::ErrorKind::InvalidData, error)))))
+    }
+}
+
+impl InputSource {
+    pub fn needs_network(&self) -> bool {
+        matches!(self, InputSource::Download | InputSource::Server { .. })
+    }
+}
+
+// Just enough HTTP/1.1 to talk to a local stand-in server, no TLS and no chunked bodies
+fn http_get(url: &str, session: Option<&str>) -> std::result::Result<String, String> {
+    let address = url.strip_prefix("http://")
+        .ok_or_else(|| format!("Only plain http:// servers are supported, got {}", url))?;
+    let (host, path) = match address.find('/') {
+        Some(idx) => address.split_at(idx),
+        None => (address, "/"),
+    };
+
+    let mut stream = TcpStream::connect(host).map_err(|error| format!("Could not connect to {}: {}", host, error))?;
+    let cookie = session.map(|session| format!("Cookie: session={}\r\n", session)).unwrap_or_default();
+    write!(stream, "GET {} HTTP/1.1\r\nHost: {}\r\n{}Connection: close\r\n\r\n", path, host, cookie)
+        .map_err(|error| error.to_string())?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).map_err(|error| error.to_string())?;
+
+    let (head, body) = response.split_once("\r\n\r\n")
+        .ok_or_else(|| "Malformed response from server".to_string())?;
+    let status = head.lines().next().unwrap_or_default();
+    match status.split(' ').nth(1) {
+        Some("200") => Ok(body.to_string()),
+        _ => Err(format!("Server answered with {}", status)),
+    }
+}
+
+pub fn is_valid_profile(profile: &str) -> bool {
+    !profile.is_empty() && profile.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|error| AocError::Io(path.to_path_buf(), error))
+}
+
+// The puzzle input tests check their answers against. Tests of days without a downloaded input are
+// skipped, so that the suite also passes without network access.
+#[cfg(test)]
+pub(crate) fn get_input(day: u32) -> Option<String> {
+    match InputProvider::default().offline(true).load(day) {
+        Ok(input) => Some(input),
+        Err(AocError::Offline(..)) => None,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_directory(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("aoc-2021-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    #[test]
+    fn cache_reads_the_input_directory() {
+        let directory = scratch_directory("cache");
+        fs::write(directory.join("input3.txt"), "00100\n11110\n").unwrap();
+
+        let provider = InputProvider::new(&directory).with_sources(vec![InputSource::Cache]).offline(false);
+        assert_eq!("00100\n11110\n", provider.load(3).unwrap());
+        assert!(matches!(provider.load(4), Err(AocError::Missing(4, _))));
+    }
+
+    #[test]
+    fn sources_are_tried_in_order() {
+        let directory = scratch_directory("order");
+        let provider = InputProvider::new(&directory).with_sources(vec![
+            InputSource::Cache,
+            InputSource::Embedded("1,2,3".to_string()),
+        ]);
+        assert_eq!("1,2,3", provider.load(6).unwrap());
+
+        fs::write(directory.join("input6.txt"), "3,4,3,1,2").unwrap();
+        assert_eq!("3,4,3,1,2", provider.load(6).unwrap());
+    }
+
+    #[test]
+    fn offline_skips_network_sources() {
+        let directory = scratch_directory("offline");
+        let provider = InputProvider::new(&directory)
+            .with_sources(vec![
+                InputSource::Cache,
+                InputSource::Server { url: "http://127.0.0.1:9".to_string(), session: None },
+                InputSource::Download,
+            ])
+            .offline(true);
+
+        let error = provider.load(7).unwrap_err();
+        assert!(matches!(error, AocError::Offline(7, _)));
+        assert!(error.to_string().contains("offline"));
+    }
+
+    #[test]
+    fn profiles_have_their_own_directory() {
+        let directory = scratch_directory("profile");
+        fs::create_dir_all(directory.join("second")).unwrap();
+        fs::write(directory.join("second").join("day05.txt"), "0,9 -> 5,9\n").unwrap();
+        fs::write(directory.join("input5.txt"), "8,0 -> 0,8\n").unwrap();
+
+        let provider = InputProvider::new(&directory)
+            .with_sources(vec![InputSource::Cache, InputSource::Download])
+            .with_profile("second")
+            .unwrap();
+        assert_eq!("0,9 -> 5,9\n", provider.load(5).unwrap());
+        assert_eq!(directory.join("second").join("answers.json"), provider.answers_path());
+        assert!(matches!(provider.load(6), Err(AocError::Missing(6, _)) | Err(AocError::Offline(6, _))));
+
+        assert!(InputProvider::default().with_profile("../secret").is_err());
+        assert!(InputProvider::default().with_profile("").is_err());
+    }
+
+    #[test]
+    fn readers_stream_lines_and_fields() {
+        let directory = scratch_directory("reader");
+        fs::write(directory.join("input1.txt"), "199\r\n200\n\n208").unwrap();
+
+        let provider = InputProvider::new(&directory).with_sources(vec![InputSource::Cache]).offline(false);
+        let lines: Vec<String> = provider.lines(1).unwrap().collect::<Result<_>>().unwrap();
+        assert_eq!(vec!["199", "200", "", "208"], lines);
+        assert!(matches!(provider.reader(2), Err(AocError::Missing(2, _))));
+
+        let fields: Vec<String> = InputReader::from_string("3,4,3\n".to_string()).split(b',').collect::<Result<_>>().unwrap();
+        assert_eq!(vec!["3", "4", "3\n"], fields);
+        let provider = provider.with_sources(vec![InputSource::File(directory.join("nothing.txt"))]);
+        assert!(matches!(provider.reader(1), Err(AocError::Io(_, _))));
+    }
+
+    #[test]
+    fn explicit_file_has_to_exist() {
+        let directory = scratch_directory("file");
+        let path = directory.join("example.txt");
+        let provider = InputProvider::default().with_sources(vec![InputSource::File(path.clone())]);
+        assert!(matches!(provider.load(1), Err(AocError::Io(_, _))));
+
+        fs::write(&path, "199\n200\n").unwrap();
+        assert_eq!("199\n200\n", provider.load(1).unwrap());
+    }
+}
@@ -1,2 +1,13 @@
-pub mod table;
+pub mod grid;
 pub mod coordinates;
+pub mod input;
+pub mod answers;
+pub mod parse;
+pub mod bench;
+pub mod search;
+pub mod sparse;
+pub mod render;
+pub mod ocr;
+pub mod image;
+pub mod gen;
+pub mod fuzz;
@@ -0,0 +1,169 @@
+use crate::error::{AocError, Result};
+use crate::utils::grid::Grid;
+
+// The block letters puzzles draw their answers in, one row per line
+struct Font {
+    height: usize,
+    letters: &'static [(char, &'static str)],
+}
+
+const SMALL: Font = Font {
+    height: 6,
+    letters: &[
+        ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+        ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+        ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+        ('E', "####\n#...\n###.\n#...\n#...\n####"),
+        ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+        ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+        ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+        ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+        ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+        ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+        ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+        ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+        ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+        ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+        ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+        ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+        ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+    ],
+};
+
+const LARGE: Font = Font {
+    height: 10,
+    letters: &[
+        ('A', "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#"),
+        ('B', "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####."),
+        ('C', ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####."),
+        ('E', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######"),
+        ('F', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+        ('G', ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#"),
+        ('H', "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#"),
+        ('J', "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###.."),
+        ('K', "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#"),
+        ('L', "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######"),
+        ('N', "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#"),
+        ('P', "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+        ('R', "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#"),
+        ('X', "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#"),
+        ('Z', "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######"),
+    ],
+};
+
+// Reads 4x6 or 6x10 letters, picked by the height of the lit part of grid, with at least one empty column
+// between letters. Empty rows and columns around the letters are ignored.
+pub fn read(grid: &Grid<bool>) -> Result<String> {
+    let rows: Vec<usize> = (0..grid.row_count()).filter(|&row| grid.row(row).contains(&true)).collect();
+    let (Some(&top), Some(&bottom)) = (rows.first(), rows.last()) else {
+        return Err(AocError::unsolvable("there are no letters to read"));
+    };
+    let font = [SMALL, LARGE].into_iter()
+        .find(|font| font.height == bottom - top + 1)
+        .ok_or_else(|| AocError::unsolvable(format!("letters are 6 or 10 dots tall, these are {}", bottom - top + 1)))?;
+
+    // Every letter is one run of columns with something lit
+    let lit: Vec<bool> = grid.columns().map(|mut column| column.any(|&dot| dot)).collect();
+    let mut text = String::new();
+    let mut start = None;
+    for column in 0..=lit.len() {
+        match (start, lit.get(column).copied().unwrap_or(false)) {
+            (None, true) => start = Some(column),
+            (Some(left), false) => {
+                let glyph: Vec<String> = (top..=bottom)
+                    .map(|row| grid.row(row)[left..column].iter().map(|&dot| if dot { '#' } else { '.' }).collect())
+                    .collect();
+                let letter = font.letters.iter()
+                    .find(|(_, pattern)| trim(pattern) == glyph)
+                    .ok_or_else(|| AocError::unsolvable(format!("letter {} is none of the known ones:\n{}", text.len() + 1, glyph.join("\n"))))?;
+                text.push(letter.0);
+                start = None;
+            },
+            _ => {},
+        }
+    }
+    Ok(text)
+}
+
+// The letters puzzles fold paper into, all of them can be drawn
+pub fn letters() -> impl Iterator<Item = char> {
+    SMALL.letters.iter().map(|&(letter, _)| letter)
+}
+
+// The reverse of read for 4x6 letters, one empty column after each of them
+pub fn draw(text: &str) -> Result<Grid<bool>> {
+    let mut grid = Grid::new(SMALL.height, 5 * text.chars().count(), false);
+    for (idx, c) in text.chars().enumerate() {
+        let (_, pattern) = SMALL.letters.iter()
+            .find(|&&(letter, _)| letter == c)
+            .ok_or_else(|| AocError::unsolvable(format!("there is no 4x6 letter {}", c)))?;
+        for (row, line) in pattern.lines().enumerate() {
+            for (column, dot) in line.chars().enumerate() {
+                grid[(row, 5 * idx + column)] = dot == '#';
+            }
+        }
+    }
+    Ok(grid)
+}
+
+// Without the empty columns on either side, so narrow letters like I match however they were cut out
+fn trim(pattern: &str) -> Vec<String> {
+    let rows: Vec<&str> = pattern.lines().collect();
+    let lit = |column: usize| rows.iter().any(|row| row.as_bytes()[column] == b'#');
+    let width = rows[0].len();
+    let left = (0..width).find(|&column| lit(column)).unwrap_or(0);
+    let right = (0..width).rfind(|&column| lit(column)).map_or(0, |column| column + 1);
+    rows.iter().map(|row| row[left..right].to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters(text: &str) -> Grid<bool> {
+        Grid::parse(text, "'#' or '.'", |c| Some(c == '#')).unwrap()
+    }
+
+    // The letters of font side by side, each followed by gap empty columns
+    fn alphabet(font: &Font, gap: usize) -> Grid<bool> {
+        let rows: Vec<String> = (0..font.height)
+            .map(|row| font.letters.iter()
+                .map(|(_, pattern)| format!("{}{}", pattern.lines().nth(row).unwrap(), ".".repeat(gap)))
+                .collect())
+            .collect();
+        letters(&rows.join("\n"))
+    }
+
+    #[test]
+    fn reads_small_letters() {
+        let text = "\
+            ......................\n\
+            .#..#.###..####.#.....\n\
+            .#..#..#...#....#.....\n\
+            .####..#...###..#.....\n\
+            .#..#..#...#....#.....\n\
+            .#..#..#...#....#.....\n\
+            .#..#.###..####.####..\n";
+        assert_eq!("HIEL", read(&letters(text)).unwrap());
+        assert_eq!("ABCEFGHIJKLOPRSUZ", read(&alphabet(&SMALL, 1)).unwrap());
+    }
+
+    #[test]
+    fn reads_large_letters() {
+        assert_eq!("ABCEFGHJKLNPRXZ", read(&alphabet(&LARGE, 2)).unwrap());
+    }
+
+    #[test]
+    fn drawn_letters_read_back() {
+        let text: String = super::letters().collect();
+        assert_eq!(text, read(&draw(&text).unwrap()).unwrap());
+        assert!(draw("HAT").is_err());
+    }
+
+    #[test]
+    fn unknown_shapes_are_errors() {
+        assert!(read(&letters("#...\n#...\n#...\n#...\n#...\n#...\n")).is_err());
+        assert!(read(&letters("####\n#..#\n####\n")).is_err());
+        assert!(read(&Grid::new(6, 4, false)).is_err());
+    }
+}
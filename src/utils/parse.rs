@@ -0,0 +1,131 @@
+use std::str::FromStr;
+use regex::{Captures, Regex};
+
+use crate::error::{AocError, Result};
+
+// Parses every non-empty line, errors point at the line they were found on
+pub fn lines<T>(input: &str, mut parse: impl FnMut(&str) -> Result<T>) -> Result<Vec<T>> {
+    input.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(idx, line)| parse(line).map_err(|error| error.at_line(idx + 1)))
+        .collect()
+}
+
+// Lazy version of lines for inputs that are read line by line, see InputReader::lines
+pub fn stream<T>(lines: impl Iterator<Item = Result<String>>, mut parse: impl FnMut(&str) -> Result<T>) -> impl Iterator<Item = Result<T>> {
+    lines.enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(line) if line.is_empty()))
+        .map(move |(idx, line)| line.and_then(|line| parse(&line).map_err(|error| error.at_line(idx + 1))))
+}
+
+// Column is where field starts in its line
+pub fn number<T: FromStr>(field: &str, column: usize) -> Result<T> {
+    field.parse()
+        .map_err(|_| AocError::parse(column, format!("expected a number, got '{}'", field)))
+}
+
+pub fn numbers<T: FromStr>(line: &str, separator: char) -> Result<Vec<T>> {
+    fields(line, separator)
+        .map(|(column, field)| number(field, column))
+        .collect()
+}
+
+// The fields between separators with the column they start at
+pub fn fields(line: &str, separator: char) -> impl Iterator<Item = (usize, &str)> {
+    let mut column = 1;
+    line.split(separator).map(move |field| {
+        let start = column;
+        column += field.len() + separator.len_utf8();
+        (start, field)
+    })
+}
+
+// Whitespace separated words with the column they start at
+pub fn words(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut offset = 0;
+    std::iter::from_fn(move || {
+        let rest = &line[offset..];
+        let start = rest.find(|c: char| !c.is_whitespace())?;
+        let end = rest[start..].find(char::is_whitespace).map_or(rest.len(), |len| start + len);
+        let word = (offset + start + 1, &rest[start..end]);
+        offset += end;
+        Some(word)
+    })
+}
+
+pub fn captures<'a>(re: &Regex, line: &'a str, expected: &str) -> Result<Captures<'a>> {
+    re.captures(line)
+        .ok_or_else(|| AocError::parse(1, format!("expected {}, got '{}'", expected, line)))
+}
+
+// Column at which group idx starts, 1 if the group didn't take part in the match
+pub fn capture_column(captured: &Captures, idx: usize) -> usize {
+    captured.get(idx).map_or(1, |group| group.start() + 1)
+}
+
+pub fn capture<T: FromStr>(captured: &Captures, idx: usize) -> Result<T> {
+    let field = captured.get(idx).map_or("", |group| group.as_str());
+    number(field, capture_column(captured, idx))
+}
+
+// Rows of single character cells, all of them as wide as the first one
+pub fn grid<T>(input: &str, expected: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Vec<Vec<T>>> {
+    let mut width = None;
+    let rows = lines(input, |line| {
+        let row = line.chars()
+            .enumerate()
+            .map(|(idx, c)| cell(c)
+                .ok_or_else(|| AocError::parse(idx + 1, format!("expected {}, got '{}'", expected, c))))
+            .collect::<Result<Vec<_>>>()?;
+        match width {
+            Some(width) if width != row.len() => Err(AocError::parse(1, format!("expected {} cells like the first row, got {}", width, row.len()))),
+            _ => {
+                width = Some(row.len());
+                Ok(row)
+            },
+        }
+    })?;
+    if rows.is_empty() {
+        return Err(AocError::parse(1, "expected at least one row"));
+    }
+    Ok(rows)
+}
+
+pub fn digit(c: char) -> Option<u32> {
+    c.to_digit(10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_point_at_line_and_column() {
+        let error = lines("1\n\n2,x\n", |line| numbers::<u32>(line, ',')).unwrap_err();
+        assert!(matches!(error, AocError::Parse { line: 3, column: 3, .. }), "{}", error);
+        assert_eq!(vec![vec![1, 2], vec![3]], lines("1,2\n3\n", |line| numbers::<u32>(line, ',')).unwrap());
+
+        let streamed: Vec<Result<u32>> = stream(["1", "", "x"].map(|line| Ok(line.to_string())).into_iter(), |line| number(line, 1)).collect();
+        assert!(matches!(streamed[..], [Ok(1), Err(AocError::Parse { line: 3, column: 1, .. })]));
+    }
+
+    #[test]
+    fn fields_know_their_column() {
+        assert_eq!(vec![(3, "22"), (6, "13"), (11, "7")], words("  22 13   7").collect::<Vec<_>>());
+        assert!(matches!(numbers::<u32>("10,200,x", ','), Err(AocError::Parse { column: 8, .. })));
+
+        let re = Regex::new(r"^x=(-?\d+)\.\.(\S+)$").unwrap();
+        let captured = captures(&re, "x=-3..y", "'x=<from>..<to>'").unwrap();
+        assert_eq!(-3, capture::<i32>(&captured, 1).unwrap());
+        assert!(matches!(capture::<i32>(&captured, 2), Err(AocError::Parse { column: 7, .. })));
+    }
+
+    #[test]
+    fn grids_have_to_be_rectangular() {
+        assert_eq!(vec![vec![1, 2], vec![3, 4]], grid("12\n34\n", "a digit", digit).unwrap());
+        assert!(matches!(grid("12\n3a\n", "a digit", digit), Err(AocError::Parse { line: 2, column: 2, .. })));
+        assert!(matches!(grid("12\n345\n", "a digit", digit), Err(AocError::Parse { line: 2, .. })));
+        assert!(grid("", "a digit", digit).is_err());
+    }
+}
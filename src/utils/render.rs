@@ -0,0 +1,206 @@
+use std::fmt::Write;
+
+use crate::utils::{grid::{Grid, Position}, sparse::{Point, SparseGrid}};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Colour {
+    fn paint(self, glyph: char) -> String {
+        let code = match self {
+            Colour::Red => 31,
+            Colour::Green => 32,
+            Colour::Yellow => 33,
+            Colour::Blue => 34,
+            Colour::Magenta => 35,
+            Colour::Cyan => 36,
+            Colour::White => 37,
+        };
+        format!("\x1b[{}m{}\x1b[0m", code, glyph)
+    }
+}
+
+// How on and off cells of boolean grids and point sets are drawn
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Glyphs {
+    pub on: char,
+    pub off: char,
+}
+
+pub const ASCII: Glyphs = Glyphs { on: '#', off: '.' };
+pub const BLOCKS: Glyphs = Glyphs { on: '█', off: ' ' };
+
+type CellFn<'a, T, R> = Box<dyn Fn(&T) -> R + 'a>;
+
+// Draws one character per cell and one line per row
+pub struct Renderer<'a, T> {
+    glyph: CellFn<'a, T, char>,
+    colour: Option<CellFn<'a, T, Option<Colour>>>,
+    blank: Option<CellFn<'a, T, bool>>,
+}
+
+impl<'a, T> Renderer<'a, T> {
+    pub fn new(glyph: impl Fn(&T) -> char + 'a) -> Self {
+        Renderer { glyph: Box::new(glyph), colour: None, blank: None }
+    }
+
+    // Wraps cells in ANSI escapes, cells without a colour stay plain
+    pub fn colour(mut self, colour: impl Fn(&T) -> Option<Colour> + 'a) -> Self {
+        self.colour = Some(Box::new(colour));
+        self
+    }
+
+    // Leaves out the rows and columns around the cells that are not blank
+    pub fn crop(mut self, blank: impl Fn(&T) -> bool + 'a) -> Self {
+        self.blank = Some(Box::new(blank));
+        self
+    }
+
+    pub fn grid(&self, grid: &Grid<T>) -> String {
+        let mut out = String::new();
+        if let Some((top_left, bottom_right)) = self.bounds(grid) {
+            for row in top_left.0..=bottom_right.0 {
+                for column in top_left.1..=bottom_right.1 {
+                    out.push_str(&self.cell(&grid[(row, column)], None));
+                }
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    // Cells that changed are coloured in after, or without colours marked in a third panel
+    pub fn diff(&self, before: &Grid<T>, after: &Grid<T>) -> String
+    where
+        T: PartialEq,
+    {
+        assert_eq!(
+            (before.row_count(), before.column_count()), (after.row_count(), after.column_count()),
+            "only grids of the same size can be compared",
+        );
+        let bounds = match (self.bounds(before), self.bounds(after)) {
+            (Some(a), Some(b)) => Some(((a.0.0.min(b.0.0), a.0.1.min(b.0.1)), (a.1.0.max(b.1.0), a.1.1.max(b.1.1)))),
+            (a, b) => a.or(b),
+        };
+        let Some((top_left, bottom_right)) = bounds else {
+            return String::new();
+        };
+
+        let mut out = String::new();
+        for row in top_left.0..=bottom_right.0 {
+            let columns = top_left.1..=bottom_right.1;
+            let changed = |column: &usize| before[(row, *column)] != after[(row, *column)];
+            let before_row: String = columns.clone().map(|column| self.cell(&before[(row, column)], None)).collect();
+            let after_row: String = columns.clone()
+                .map(|column| self.cell(&after[(row, column)], changed(&column).then_some(Colour::Red)))
+                .collect();
+            write!(out, "{}  {}", before_row, after_row).unwrap();
+            if self.colour.is_none() {
+                let marks: String = columns.map(|column| if changed(&column) { '^' } else { ' ' }).collect();
+                write!(out, "  {}", marks.trim_end()).unwrap();
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    // Corners of the part of grid to draw, both inclusive
+    fn bounds(&self, grid: &Grid<T>) -> Option<(Position, Position)> {
+        let Some(blank) = &self.blank else {
+            return (grid.row_count() > 0 && grid.column_count() > 0)
+                .then(|| ((0, 0), (grid.row_count() - 1, grid.column_count() - 1)));
+        };
+        grid.iter()
+            .filter(|(_, cell)| !blank(cell))
+            .fold(None, |bounds, ((row, column), _)| Some(match bounds {
+                Some(((top, left), (bottom, right))) => ((row.min(top), column.min(left)), (row.max(bottom), column.max(right))),
+                None => ((row, column), (row, column)),
+            }))
+    }
+
+    // Highlight overrides the colour of the cell, but only when colours are on
+    fn cell(&self, cell: &T, highlight: Option<Colour>) -> String {
+        let glyph = (self.glyph)(cell);
+        match &self.colour {
+            Some(colour) => match highlight.or_else(|| colour(cell)) {
+                Some(colour) => colour.paint(glyph),
+                None => glyph.to_string(),
+            },
+            None => glyph.to_string(),
+        }
+    }
+}
+
+impl<'a, T: Clone> Renderer<'a, T> {
+    // Always cropped to the set cells, an unbounded grid has no edges to draw up to
+    pub fn sparse(&self, grid: &SparseGrid<T>) -> String {
+        self.grid(&grid.to_grid())
+    }
+}
+
+impl<'a> Renderer<'a, bool> {
+    pub fn bools(glyphs: Glyphs) -> Self {
+        Renderer::new(move |&on| if on { glyphs.on } else { glyphs.off })
+    }
+}
+
+// The bounding box of the points, with every point in it drawn as on
+pub fn points(points: impl IntoIterator<Item = Point>, glyphs: Glyphs) -> String {
+    let mut grid = SparseGrid::new(false);
+    for point in points {
+        grid.insert(point, true);
+    }
+    Renderer::bools(glyphs).sparse(&grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::coordinates::Vector;
+
+    #[test]
+    fn renders_cropped_and_coloured() {
+        let grid = Grid::parse_digits("000\n010\n023\n").unwrap();
+        let digits = Renderer::new(|&digit: &u32| char::from_digit(digit, 10).unwrap());
+        assert_eq!("000\n010\n023\n", digits.grid(&grid));
+
+        let cropped = digits.crop(|&digit| digit == 0);
+        assert_eq!("10\n23\n", cropped.grid(&grid));
+        assert_eq!("", cropped.grid(&Grid::new(2, 2, 0)));
+
+        let coloured = Renderer::new(|&digit: &u32| char::from_digit(digit, 10).unwrap())
+            .colour(|&digit| (digit == 3).then_some(Colour::Green))
+            .crop(|&digit| digit < 2);
+        assert_eq!("2\x1b[32m3\x1b[0m\n", coloured.grid(&grid));
+    }
+
+    #[test]
+    fn renders_sparse_grids_and_points() {
+        assert_eq!("#.\n.#\n", points([Vector::from((-5, 3)), Vector::from((-4, 4))], ASCII));
+        assert_eq!("", points([], BLOCKS));
+
+        let mut grid = SparseGrid::new(0);
+        grid[Vector::from((2, 0))] = 7;
+        grid[Vector::from((0, 1))] = 1;
+        assert_eq!("  7\n1  \n", Renderer::new(|&value: &u32| if value == 0 { ' ' } else { char::from_digit(value, 10).unwrap() }).sparse(&grid));
+    }
+
+    #[test]
+    fn diffs_mark_changed_cells() {
+        let before = Grid::parse("#..\n...\n", "'#' or '.'", |c| Some(c == '#')).unwrap();
+        let after = Grid::parse("#.#\n.#.\n", "'#' or '.'", |c| Some(c == '#')).unwrap();
+        assert_eq!("#..  #.#    ^\n...  .#.   ^\n", Renderer::bools(ASCII).diff(&before, &after));
+        assert_eq!("#  .  ^\n", Renderer::bools(ASCII).crop(|&on| !on).diff(&before, &Grid::new(2, 3, false)));
+
+        let coloured = Renderer::bools(ASCII).colour(|_| None);
+        assert_eq!(Some("#..  #.\x1b[31m#\x1b[0m"), coloured.diff(&before, &after).lines().next());
+    }
+}
@@ -0,0 +1,344 @@
+use std::{cmp::Reverse, collections::{BinaryHeap, HashMap, VecDeque}, hash::Hash, ops::Add};
+
+use num::Zero;
+
+use crate::utils::grid::{Grid, Position};
+
+// Successors are handed in as closures, so the same search runs on grids, adjacency maps and
+// implicit graphs alike, see grid_steps and edges for the common cases
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    // Nodes taken off the frontier
+    pub expanded: usize,
+    // Distinct nodes seen, including the start
+    pub discovered: usize,
+    pub max_frontier: usize,
+}
+
+impl Stats {
+    fn track(&mut self, frontier: usize) {
+        self.max_frontier = self.max_frontier.max(frontier);
+    }
+
+    // Totals of two searches, the frontier is the larger of the two since they ran one after the other
+    fn add(&mut self, other: Stats) {
+        self.expanded += other.expanded;
+        self.discovered += other.discovered;
+        self.track(other.max_frontier);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<N, C> {
+    // From the start up to and including the goal
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Search<N, C> {
+    pub path: Option<Path<N, C>>,
+    pub stats: Stats,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region<N> {
+    // In the order they were reached, starting with the start
+    pub nodes: Vec<N>,
+    pub stats: Stats,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Components<N> {
+    // Each in the order its flood fill reached it
+    pub groups: Vec<Vec<N>>,
+    // Summed over all flood fills
+    pub stats: Stats,
+}
+
+// Every node seen so far, with the one it was reached from and what it cost to get there
+struct Tree<N, C> {
+    nodes: Vec<(N, usize, C)>,
+    index: HashMap<N, usize>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Tree<N, C> {
+    fn new(start: N, cost: C) -> Self {
+        Tree {
+            index: HashMap::from([(start.clone(), 0)]),
+            nodes: vec![(start, 0, cost)],
+        }
+    }
+
+    fn node(&self, idx: usize) -> &N {
+        &self.nodes[idx].0
+    }
+
+    fn cost(&self, idx: usize) -> C {
+        self.nodes[idx].2
+    }
+
+    fn lookup(&self, node: &N) -> Option<usize> {
+        self.index.get(node).copied()
+    }
+
+    // Index of node if it was not seen before
+    fn insert(&mut self, node: N, parent: usize, cost: C) -> Option<usize> {
+        if self.index.contains_key(&node) {
+            return None;
+        }
+        self.index.insert(node.clone(), self.nodes.len());
+        self.nodes.push((node, parent, cost));
+        Some(self.nodes.len() - 1)
+    }
+
+    fn reparent(&mut self, idx: usize, parent: usize, cost: C) {
+        self.nodes[idx].1 = parent;
+        self.nodes[idx].2 = cost;
+    }
+
+    fn path(&self, mut idx: usize) -> Path<N, C> {
+        let cost = self.cost(idx);
+        let mut nodes = vec![self.node(idx).clone()];
+        while idx != 0 {
+            idx = self.nodes[idx].1;
+            nodes.push(self.node(idx).clone());
+        }
+        nodes.reverse();
+        Path { nodes, cost }
+    }
+
+    fn into_nodes(self) -> Vec<N> {
+        self.nodes.into_iter().map(|(node, _, _)| node).collect()
+    }
+}
+
+// Fewest steps from start to the first node that satisfies success
+pub fn bfs<N, I>(start: N, successors: impl FnMut(&N) -> I, success: impl FnMut(&N) -> bool) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let (goal, tree, stats) = explore(start, successors, success, VecDeque::pop_front);
+    Search { path: goal.map(|goal| tree.path(goal)), stats }
+}
+
+// Some path to the first node that satisfies success, usually not the shortest
+pub fn dfs<N, I>(start: N, successors: impl FnMut(&N) -> I, success: impl FnMut(&N) -> bool) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let (goal, tree, stats) = explore(start, successors, success, VecDeque::pop_back);
+    Search { path: goal.map(|goal| tree.path(goal)), stats }
+}
+
+// Breadth or depth first, depending on which end of the frontier next takes from
+fn explore<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut success: impl FnMut(&N) -> bool,
+    next: fn(&mut VecDeque<usize>) -> Option<usize>,
+) -> (Option<usize>, Tree<N, usize>, Stats)
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut tree = Tree::new(start, 0);
+    let mut frontier = VecDeque::from([0]);
+    let mut stats = Stats { discovered: 1, max_frontier: 1, ..Stats::default() };
+    while let Some(idx) = next(&mut frontier) {
+        stats.expanded += 1;
+        if success(tree.node(idx)) {
+            return (Some(idx), tree, stats);
+        }
+        let steps = tree.cost(idx) + 1;
+        for successor in successors(&tree.node(idx).clone()) {
+            if let Some(successor) = tree.insert(successor, idx, steps) {
+                frontier.push_back(successor);
+                stats.discovered += 1;
+            }
+        }
+        stats.track(frontier.len());
+    }
+    (None, tree, stats)
+}
+
+// Cheapest path to the first node that satisfies success, costs must not be negative
+pub fn dijkstra<N, C, I>(start: N, successors: impl FnMut(&N) -> I, success: impl FnMut(&N) -> bool) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::zero(), success)
+}
+
+// Dijkstra guided by heuristic, which must never overestimate the remaining cost
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut success: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut frontier = BinaryHeap::from([Reverse((heuristic(&start), C::zero(), 0))]);
+    let mut tree = Tree::new(start, C::zero());
+    let mut stats = Stats { discovered: 1, max_frontier: 1, ..Stats::default() };
+    while let Some(Reverse((_, cost, idx))) = frontier.pop() {
+        // Stale entry, the node was reached more cheaply after this one was queued
+        if cost > tree.cost(idx) {
+            continue;
+        }
+        stats.expanded += 1;
+        if success(tree.node(idx)) {
+            return Search { path: Some(tree.path(idx)), stats };
+        }
+        for (successor, step) in successors(&tree.node(idx).clone()) {
+            let cost = cost + step;
+            let successor_idx = match tree.lookup(&successor) {
+                Some(known) if tree.cost(known) <= cost => continue,
+                Some(known) => {
+                    tree.reparent(known, idx, cost);
+                    known
+                },
+                None => {
+                    stats.discovered += 1;
+                    tree.insert(successor, idx, cost).unwrap()
+                },
+            };
+            frontier.push(Reverse((cost + heuristic(tree.node(successor_idx)), cost, successor_idx)));
+        }
+        stats.track(frontier.len());
+    }
+    Search { path: None, stats }
+}
+
+// Every node reachable from start
+pub fn flood_fill<N, I>(start: N, successors: impl FnMut(&N) -> I) -> Region<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let (_, tree, stats) = explore(start, successors, |_| false, VecDeque::pop_front);
+    Region { nodes: tree.into_nodes(), stats }
+}
+
+// Splits nodes into groups that can reach each other, successors has to be symmetric
+pub fn components<N, I>(nodes: impl IntoIterator<Item = N>, mut successors: impl FnMut(&N) -> I) -> Components<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut assigned = HashMap::new();
+    let mut groups = Vec::new();
+    let mut stats = Stats::default();
+    for node in nodes {
+        if assigned.contains_key(&node) {
+            continue;
+        }
+        let region = flood_fill(node, &mut successors);
+        for member in &region.nodes {
+            assigned.insert(member.clone(), groups.len());
+        }
+        groups.push(region.nodes);
+        stats.add(region.stats);
+    }
+    Components { groups, stats }
+}
+
+pub fn manhattan((row, column): Position, (other_row, other_column): Position) -> usize {
+    row.abs_diff(other_row) + column.abs_diff(other_column)
+}
+
+// Orthogonal steps onto cells that passable accepts
+pub fn grid_steps<'a, T>(grid: &'a Grid<T>, passable: impl Fn(&T) -> bool + 'a) -> impl FnMut(&Position) -> Vec<Position> + 'a {
+    move |&position| grid.neighbours4(position)
+        .filter(|(_, cell)| passable(cell))
+        .map(|(neighbour, _)| neighbour)
+        .collect()
+}
+
+// Successors in an adjacency map, nodes without an entry are dead ends
+pub fn edges<N: Clone + Eq + Hash>(map: &HashMap<N, Vec<N>>) -> impl FnMut(&N) -> Vec<N> + '_ {
+    move |node| map.get(node).cloned().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "\
+        .....\n\
+        ###.#\n\
+        #...#\n\
+        .#.##\n\
+        ...#.\n";
+
+    fn maze() -> Grid<bool> {
+        Grid::parse(MAZE, "'#' or '.'", |c| match c {
+            '.' => Some(true),
+            '#' => Some(false),
+            _ => None,
+        }).unwrap()
+    }
+
+    #[test]
+    fn bfs_and_astar_find_shortest_paths() {
+        let maze = maze();
+        let goal = (4, 0);
+        let shortest = bfs((0, 0), grid_steps(&maze, |&open| open), |&p| p == goal);
+        let path = shortest.path.unwrap();
+        assert_eq!(10, path.cost);
+        assert_eq!(11, path.nodes.len());
+        assert_eq!(Some(&(0, 0)), path.nodes.first());
+        assert!(path.nodes.windows(2).all(|step| manhattan(step[0], step[1]) == 1));
+
+        let mut steps = grid_steps(&maze, |&open| open);
+        let guided = astar((0, 0), |p| steps(p).into_iter().map(|n| (n, 1)), |&p| manhattan(p, goal), |&p| p == goal);
+        assert_eq!(10, guided.path.unwrap().cost);
+        assert!(guided.stats.expanded <= shortest.stats.expanded);
+
+        let blocked = dfs((0, 0), grid_steps(&maze, |&open| open), |&p| p == (4, 4));
+        assert_eq!(None, blocked.path);
+        assert_eq!(14, blocked.stats.discovered);
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheap_detours() {
+        let costs = Grid::parse_digits("1911\n1991\n1111\n").unwrap();
+        let search = dijkstra((0, 0), |&p| costs.neighbours4(p).map(|(n, &cost)| (n, cost)).collect::<Vec<_>>(), |&p| p == (0, 3));
+        let path = search.path.unwrap();
+        assert_eq!(7, path.cost);
+        assert_eq!(vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2), (2, 3), (1, 3), (0, 3)], path.nodes);
+    }
+
+    #[test]
+    fn regions_of_adjacency_maps() {
+        let map = HashMap::from([
+            ("start", vec!["a", "b"]),
+            ("a", vec!["start", "end"]),
+            ("b", vec!["start"]),
+            ("end", vec!["a"]),
+            ("c", vec!["d"]),
+            ("d", vec!["c"]),
+        ]);
+        let region = flood_fill("start", edges(&map));
+        assert_eq!(vec!["start", "a", "b", "end"], region.nodes);
+        assert_eq!(4, region.stats.expanded);
+
+        let mut components = components(["c", "start", "d", "end", "e"], edges(&map));
+        components.groups.iter_mut().for_each(|group| group.sort());
+        assert_eq!(vec![vec!["c", "d"], vec!["a", "b", "end", "start"], vec!["e"]], components.groups);
+        assert_eq!(7, components.stats.expanded);
+        assert_eq!(7, components.stats.discovered);
+
+        let path = dfs("b", edges(&map), |&node| node == "end").path.unwrap();
+        assert_eq!(vec!["b", "start", "a", "end"], path.nodes);
+    }
+}
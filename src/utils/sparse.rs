@@ -0,0 +1,179 @@
+use std::{collections::{hash_map, HashMap}, ops::{Index, IndexMut}};
+
+use crate::utils::{coordinates::Vector, grid::Grid};
+
+// Unbounded in every direction, x grows to the right and y downwards like the columns and rows of a Grid
+pub type Point = Vector<isize, 2>;
+
+// Only cells that were set are stored, everything else reads as the background
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    background: T,
+    // Smallest and largest corner of the set cells, both inclusive
+    bounds: Option<(Point, Point)>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(background: T) -> Self {
+        SparseGrid { cells: HashMap::new(), background, bounds: None }
+    }
+
+    pub fn background(&self) -> &T {
+        &self.background
+    }
+
+    // Changes every cell that was never set at once, e.g. when an automaton flips the infinite rest
+    pub fn set_background(&mut self, background: T) {
+        self.background = background;
+    }
+
+    pub fn get(&self, point: Point) -> &T {
+        self.cells.get(&point).unwrap_or(&self.background)
+    }
+
+    pub fn is_set(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (min.zip_with(point, Ord::min), max.zip_with(point, Ord::max)),
+            None => (point, point),
+        });
+        self.cells.insert(point, value)
+    }
+
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        let value = self.cells.remove(&point)?;
+        // Only a cell on the edge can shrink the bounds
+        if let Some((min, max)) = self.bounds {
+            if (0..2).any(|axis| point[axis] == min[axis] || point[axis] == max[axis]) {
+                self.bounds = self.cells.keys().fold(None, |bounds, &point| Some(match bounds {
+                    Some((min, max)) => (min.zip_with(point, Ord::min), max.zip_with(point, Ord::max)),
+                    None => (point, point),
+                }));
+            }
+        }
+        Some(value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    // Every point in the bounds grown by margin on all sides, row by row, nothing while no cell is set
+    pub fn window(&self, margin: isize) -> impl Iterator<Item = Point> {
+        self.bounds.into_iter().flat_map(move |(min, max)| (min.y() - margin..=max.y() + margin)
+            .flat_map(move |y| (min.x() - margin..=max.x() + margin).map(move |x| Vector::from((x, y)))))
+    }
+
+    // Set cells only, in no particular order
+    pub fn iter(&self) -> hash_map::Iter<'_, Point, T> {
+        self.cells.iter()
+    }
+
+    pub fn values(&self) -> hash_map::Values<'_, Point, T> {
+        self.cells.values()
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    // Covers the bounds, with the smallest corner at (0, 0) and unset cells filled with the background
+    pub fn to_grid(&self) -> Grid<T> {
+        let Some((min, max)) = self.bounds else {
+            return Grid::from_vec(Vec::new(), (0, 0));
+        };
+        let size = max - min + Vector::from((1, 1));
+        let mut grid = Grid::new(size.y() as usize, size.x() as usize, self.background.clone());
+        for (&point, value) in &self.cells {
+            grid[(point - min).map(|c| c as usize)] = value.clone();
+        }
+        grid
+    }
+}
+
+impl<T: Clone + PartialEq> SparseGrid<T> {
+    // Cells that equal the background are left unset
+    pub fn from_grid(grid: &Grid<T>, background: T) -> Self {
+        let mut sparse = SparseGrid::new(background);
+        for ((row, column), value) in grid.iter() {
+            if *value != sparse.background {
+                sparse.insert(Vector::from((column as isize, row as isize)), value.clone());
+            }
+        }
+        sparse
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SparseGrid<T> {
+    type Item = (&'a Point, &'a T);
+    type IntoIter = hash_map::Iter<'a, Point, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> Index<Point> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+    }
+}
+
+// Writing to a cell that was never set sets it to the background first
+impl<T: Clone> IndexMut<Point> for SparseGrid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        if !self.is_set(point) {
+            self.insert(point, self.background.clone());
+        }
+        self.cells.get_mut(&point).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unset_cells_read_as_background() {
+        let mut grid = SparseGrid::new(0);
+        assert_eq!(None, grid.bounds());
+        grid[Vector::from((-3, 7))] += 2;
+        grid[Vector::from((5, -1))] += 1;
+        grid[Vector::from((5, -1))] += 1;
+        assert_eq!(2, grid.len());
+        assert_eq!(2, grid[Vector::from((5, -1))]);
+        assert_eq!(0, grid[Vector::from((1_000_000, -1_000_000))]);
+        assert_eq!(Some((Vector::from((-3, -1)), Vector::from((5, 7)))), grid.bounds());
+
+        grid.set_background(9);
+        assert_eq!(9, grid[Vector::from((0, 0))]);
+        assert_eq!(Some(2), grid.remove(Vector::from((-3, 7))));
+        assert_eq!(Some((Vector::from((5, -1)), Vector::from((5, -1)))), grid.bounds());
+        assert_eq!(9, grid.window(1).count());
+    }
+
+    #[test]
+    fn converts_to_and_from_dense_grids() {
+        let dense = Grid::parse("#..\n..#\n", "'#' or '.'", |c| Some(c == '#')).unwrap();
+        let mut sparse = SparseGrid::from_grid(&dense, false);
+        assert_eq!(2, sparse.len());
+        assert!(sparse[Vector::from((2, 1))]);
+        assert_eq!(dense, sparse.to_grid());
+
+        sparse.insert(Vector::from((-1, 0)), true);
+        assert_eq!(Grid::from_vecvec(vec![vec![true, true, false, false], vec![false, false, false, true]]), sparse.to_grid());
+        assert_eq!(0, SparseGrid::<bool>::new(true).to_grid().cells().len());
+    }
+}
@@ -0,0 +1,82 @@
+// Shared by several test crates, each of which only uses part of it
+#![allow(dead_code)]
+
+use std::{
+    fs,
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    sync::{atomic::{AtomicUsize, Ordering}, Arc},
+    thread,
+};
+
+// Stand-in for the adventofcode.com input endpoint, serves tests/fixtures/day<N>.txt
+pub struct FakeAocServer {
+    url: String,
+    requests: Arc<AtomicUsize>,
+}
+
+impl FakeAocServer {
+    pub fn start() -> Self {
+        Self::serve(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures"))
+    }
+
+    pub fn serve(fixtures: PathBuf) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+
+        let counter = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                counter.fetch_add(1, Ordering::SeqCst);
+                handle(stream, &fixtures);
+            }
+        });
+
+        FakeAocServer { url, requests }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+}
+
+fn handle(mut stream: TcpStream, fixtures: &Path) {
+    let mut request_line = String::new();
+    let mut has_session = false;
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    reader.read_line(&mut request_line).unwrap();
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).unwrap() == 0 || header == "\r\n" {
+            break;
+        }
+        has_session |= header.to_lowercase().starts_with("cookie: session=");
+    }
+
+    let path = request_line.split(' ').nth(1).unwrap_or_default();
+    let day = path.strip_prefix("/2021/day/")
+        .and_then(|rest| rest.strip_suffix("/input"))
+        .and_then(|day| day.parse::<u32>().ok());
+    let (status, body) = match day {
+        _ if !has_session => ("400 Bad Request", "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n".to_string()),
+        Some(day) => match fs::read_to_string(fixtures.join(format!("day{}.txt", day))) {
+            Ok(input) => ("200 OK", input),
+            Err(_) => ("404 Not Found", "404 Not Found\n".to_string()),
+        },
+        None => ("404 Not Found", "404 Not Found\n".to_string()),
+    };
+    write!(stream, "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body).unwrap();
+}
+
+pub fn scratch_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("aoc-2021-it-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+    directory
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use std::{fs, process::Command};
+
+use aoc_2021::{get_day, utils::gen};
+use common::scratch_directory;
+
+// Small sizes, so even the brute force days stay quick
+const SIZES: [usize; 25] = [50, 50, 50, 5, 50, 20, 50, 20, 12, 20, 10, 4, 3, 10, 15, 20, 30, 8, 3, 10, 0, 20, 0, 0, 12];
+
+#[test]
+fn generated_inputs_are_solvable() {
+    for (day, size) in (1..=25).zip(SIZES) {
+        // Days 23 and 24 have none
+        let Some(generator) = gen::generator(day) else { continue };
+        for seed in 0..3 {
+            let input = generator.generate(seed, size);
+            if let Err(error) = get_day(day).unwrap().solve_input(&input) {
+                panic!("day {} with seed {} failed: {}\n{}", day, seed, error, input);
+            }
+        }
+    }
+}
+
+#[test]
+fn generated_origami_spells_a_code() {
+    let input = gen::generator(13).unwrap().generate(7, 5);
+    let (_, code) = get_day(13).unwrap().solve_input(&input).unwrap();
+    assert_eq!(5, code.to_string().len());
+    assert!(code.to_string().chars().all(|c| c.is_ascii_uppercase()));
+}
+
+#[test]
+fn generate_writes_into_a_profile() {
+    let directory = scratch_directory("generate");
+    let aoc = |args: &[&str]| Command::new(env!("CARGO_BIN_EXE_aoc-2021"))
+        .args(args)
+        .args(["--offline", "--input-dir"])
+        .arg(&directory)
+        .output()
+        .unwrap();
+
+    let output = aoc(&["generate", "2", "--size", "3", "--seed", "5"]);
+    assert!(output.status.success());
+    assert_eq!(3, String::from_utf8(output.stdout.clone()).unwrap().lines().count());
+    assert_eq!(output.stdout, aoc(&["generate", "2", "--size", "3", "--seed", "5"]).stdout);
+
+    let output = aoc(&["generate", "1", "--size", "100", "--profile", "synthetic"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(100, fs::read_to_string(directory.join("synthetic").join("day01.txt")).unwrap().lines().count());
+    let output = aoc(&["part", "1", "1", "--profile", "synthetic"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
@@ -0,0 +1,175 @@
+mod common;
+
+use std::{fs, path::Path, process::Command};
+
+use aoc_2021::{error::AocError, get_day, solution::Answer, utils::input::{InputProvider, InputSource}};
+use common::{scratch_directory, FakeAocServer};
+
+fn server_provider(server: &FakeAocServer, directory: &std::path::Path) -> InputProvider {
+    InputProvider::new(directory)
+        .with_sources(vec![
+            InputSource::Cache,
+            InputSource::Server { url: server.url().to_string(), session: Some("fixture".to_string()) },
+        ])
+        .offline(false)
+}
+
+#[test]
+fn downloads_into_the_cache_and_solves() {
+    let server = FakeAocServer::start();
+    let directory = scratch_directory("download");
+    let provider = server_provider(&server, &directory);
+
+    let input = provider.load(1).unwrap();
+    assert_eq!(1, server.requests());
+    assert!(directory.join("input1.txt").exists());
+
+    assert_eq!(input, provider.load(1).unwrap());
+    assert_eq!(1, server.requests());
+
+    let day = get_day(1).unwrap();
+    assert_eq!((Answer::Number(7), Answer::Number(5)), day.solve_input(&input).unwrap());
+}
+
+#[test]
+fn offline_never_contacts_the_server() {
+    let server = FakeAocServer::start();
+    let directory = scratch_directory("offline");
+    let provider = server_provider(&server, &directory).offline(true);
+
+    let error = provider.load(2).unwrap_err();
+    assert!(matches!(error, AocError::Offline(2, _)));
+    assert!(error.to_string().contains("missing"));
+    assert_eq!(0, server.requests());
+}
+
+#[test]
+fn unknown_inputs_are_download_errors() {
+    let server = FakeAocServer::start();
+    let directory = scratch_directory("unknown");
+    let provider = server_provider(&server, &directory);
+
+    assert!(matches!(provider.load(25), Err(AocError::Download(25, _))));
+    assert!(!directory.join("input25.txt").exists());
+}
+
+#[test]
+fn missing_session_is_rejected() {
+    let server = FakeAocServer::start();
+    let directory = scratch_directory("session");
+    let provider = InputProvider::new(&directory)
+        .with_sources(vec![InputSource::Server { url: server.url().to_string(), session: None }])
+        .offline(false);
+
+    let error = provider.load(1).unwrap_err();
+    assert!(error.to_string().contains("400"));
+}
+
+#[test]
+fn cli_runs_against_the_server() {
+    let server = FakeAocServer::start();
+    let directory = scratch_directory("cli");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc-2021"))
+        .args(["run", "2,6", "--server", server.url(), "--input-dir"])
+        .arg(&directory)
+        .env("AOC_SESSION", "fixture")
+        .env_remove("AOC_OFFLINE")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("Part 1: 150"));
+    assert!(stdout.contains("Part 2: 26984457539"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc-2021"))
+        .args(["run", "1", "--offline", "--server", server.url(), "--input-dir"])
+        .arg(&directory)
+        .output()
+        .unwrap();
+    assert_eq!(Some(1), output.status.code());
+    assert!(String::from_utf8(output.stdout).unwrap().contains("offline mode"));
+}
+
+#[test]
+fn parallel_runs_print_in_day_order() {
+    let directory = scratch_directory("jobs");
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    for day in [1, 2, 6] {
+        fs::copy(fixtures.join(format!("day{}.txt", day)), directory.join(format!("input{}.txt", day))).unwrap();
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc-2021"))
+        .args(["run", "1,2,6", "--jobs", "3", "--offline", "--input-dir"])
+        .arg(&directory)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "{}", stdout);
+    let position = |text: &str| stdout.find(text).unwrap_or_else(|| panic!("{} missing from {}", text, stdout));
+    assert!(position("Running day 1:") < position("Running day 2:"));
+    assert!(position("Running day 2:") < position("Running day 6:"));
+    assert!(position("Running day 6:") < position("Total on 3 threads"));
+}
+
+#[test]
+fn streaming_gives_the_same_answers() {
+    let directory = scratch_directory("stream");
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    for day in [1, 2, 6] {
+        fs::copy(fixtures.join(format!("day{}.txt", day)), directory.join(format!("input{}.txt", day))).unwrap();
+    }
+
+    let run = |stream: bool| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_aoc-2021"));
+        command.args(["run", "1,2,6", "--format", "json", "--offline", "--input-dir"]).arg(&directory);
+        if stream {
+            command.arg("--stream");
+        }
+        let output = command.output().unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+        let records: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        records.as_array().unwrap().iter().map(|record| record["answer"].clone()).collect::<Vec<_>>()
+    };
+    let streamed = run(true);
+    assert_eq!(run(false), streamed);
+    assert_eq!("7", streamed[0]);
+    assert_eq!("26984457539", streamed[5]);
+}
+
+#[test]
+fn sonar_sweeps_the_day1_input() {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/day1.txt");
+    let sweep = |window: &str| {
+        let output = Command::new(env!("CARGO_BIN_EXE_aoc-2021"))
+            .args(["sonar", "--window", window, "--changes", "--format", "json", "--input"])
+            .arg(&fixture)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+    };
+    // Windows of one and three soundings are the two parts of the puzzle
+    assert_eq!(7, sweep("1")["increases"]);
+    let triple = sweep("3");
+    assert_eq!(5, triple["increases"]);
+    assert_eq!(4, triple["longest_rise"]);
+    assert_eq!(7, triple["changes"].as_array().unwrap().len());
+    assert_eq!(serde_json::json!({ "line": 5, "change": "unchanged" }), triple["changes"][1]);
+}
+
+#[test]
+fn sonar_closes_the_json_of_a_failed_sweep() {
+    let directory = scratch_directory("sonar");
+    let input = directory.join("day1.txt");
+    fs::write(&input, "199\n200\n208\n210\ndeep\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc-2021"))
+        .args(["sonar", "--window", "1", "--changes", "--format", "json", "--input"])
+        .arg(&input)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let sweep = serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap();
+    assert_eq!(3, sweep["changes"].as_array().unwrap().len());
+    assert!(sweep["error"].as_str().unwrap().contains("line 5"), "{}", sweep);
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use std::{fs, path::Path, process::{Command, Output}};
+
+use common::scratch_directory;
+
+fn check(directory: &Path, profile: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc-2021"))
+        .args(["check", "1,6", "--offline", "--profile", profile, "--input-dir"])
+        .arg(directory)
+        .output()
+        .unwrap()
+}
+
+fn add_profile(directory: &Path, profile: &str, answers: &str) {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    fs::create_dir_all(directory.join(profile)).unwrap();
+    fs::copy(fixtures.join("day1.txt"), directory.join(profile).join("day01.txt")).unwrap();
+    fs::copy(fixtures.join("day6.txt"), directory.join(profile).join("day06.txt")).unwrap();
+    fs::write(directory.join(profile).join("answers.json"), answers).unwrap();
+}
+
+#[test]
+fn check_compares_against_the_profile_answers() {
+    let directory = scratch_directory("profiles");
+    add_profile(&directory, "example", r#"{
+        "1": { "part1": "7", "part2": "5" },
+        "6": { "part1": "5934" }
+    }"#);
+    add_profile(&directory, "wrong", r#"{ "6": { "part2": "42" } }"#);
+
+    let output = check(&directory, "example");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("Day  1 part 2: pass"), "{}", stdout);
+    assert!(stdout.contains("Day  6 part 2: missing (got 26984457539)"), "{}", stdout);
+    assert!(stdout.contains("3 passed, 0 failed, 0 errors, 1 missing"), "{}", stdout);
+
+    let output = check(&directory, "wrong");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(Some(1), output.status.code());
+    assert!(stdout.contains("Day  6 part 2: FAIL expected 42, got 26984457539"), "{}", stdout);
+}
+
+#[test]
+fn unknown_profiles_have_no_inputs() {
+    let directory = scratch_directory("no-profile");
+    let output = check(&directory, "nobody");
+    assert_eq!(Some(1), output.status.code());
+    assert!(String::from_utf8(output.stdout).unwrap().contains("nobody/day01.txt"));
+
+    let output = check(&directory, "../escape");
+    assert_eq!(Some(2), output.status.code());
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use std::{fs, path::Path, process::{Command, Output}};
+
+use common::scratch_directory;
+
+fn visualize(directory: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc-2021"))
+        .arg("visualize")
+        .args(args)
+        .args(["--delay", "0", "--offline", "--input-dir"])
+        .arg(directory)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn simulations_play_their_frames() {
+    let directory = scratch_directory("visualize");
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    fs::copy(fixtures.join("day11.txt"), directory.join("input11.txt")).unwrap();
+    fs::copy(fixtures.join("day1.txt"), directory.join("input1.txt")).unwrap();
+
+    let output = visualize(&directory, &["11", "--max-frames", "3"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.starts_with("Step 0\n5483143223\n"), "{}", stdout);
+    assert!(stdout.contains("Step 2, 35 flashed"), "{}", stdout);
+    assert!(!stdout.contains("Step 3"), "{}", stdout);
+
+    // The example synchronises in step 195, after which there is nothing left to show
+    let output = visualize(&directory, &["11"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.trim_end().lines().rev().nth(10).unwrap().starts_with("Step 195, 100 flashed"), "{}", stdout);
+
+    let output = visualize(&directory, &["1"]);
+    assert_eq!(Some(1), output.status.code());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("not a simulation"));
+}
+
+#[test]
+fn simulations_export_pictures() {
+    let directory = scratch_directory("export");
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    fs::copy(fixtures.join("day11.txt"), directory.join("input11.txt")).unwrap();
+
+    let export = |args: &[&str]| Command::new(env!("CARGO_BIN_EXE_aoc-2021"))
+        .arg("export")
+        .args(args)
+        .args(["--offline", "--input-dir"])
+        .arg(&directory)
+        .output()
+        .unwrap();
+
+    let gif = directory.join("octopuses.gif");
+    let output = export(&["11", gif.to_str().unwrap(), "--max-frames", "10"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8(output.stdout).unwrap().contains("Wrote 10 frames"));
+    assert!(fs::read(&gif).unwrap().starts_with(b"GIF89a"));
+
+    let pngs = directory.join("frames");
+    let output = export(&["11", pngs.to_str().unwrap(), "--max-frames", "2", "--scale", "1"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(fs::read(pngs.join("frame0001.png")).unwrap().starts_with(b"\x89PNG"));
+    assert!(!pngs.join("frame0002.png").exists());
+}